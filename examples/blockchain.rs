@@ -1,68 +1,82 @@
 use murray_rs::{
-  BlockchainError, GetAddressParams, GetBlockParams, GetTransactionParams, Murray, PostTransactionParams
+  BlockRef, GetAddressParams, GetTransactionParams, Murray, MurrayError, PostTransactionParams,
 };
 
-fn blockchain_example() -> std::result::Result<(), BlockchainError> {
+async fn blockchain_example() -> std::result::Result<(), MurrayError> {
   let murray = Murray::default();
 
-  let a = murray.blockchain.get_block(GetBlockParams {
-    hash: None,
-    height: Some(500000),
-  })?;
+  let a = murray
+    .blockchain
+    .get_block(BlockRef::Height(500000))
+    .await?;
   println!("{:?}\n", a);
 
-  let b = murray.blockchain.get_block2time(GetBlockParams {
-    hash: None,
-    height: Some(500000),
-  })?;
+  let b = murray
+    .blockchain
+    .get_block2time(BlockRef::Height(500000))
+    .await?;
   println!("{:?}\n", b);
 
-  let c = murray.blockchain.get_fees_recommended()?;
+  let c = murray.blockchain.get_fees_recommended().await?;
   println!("{:?}\n", c);
 
-  let d = murray.blockchain.get_fees_mempool_blocks()?;
+  let d = murray.blockchain.get_fees_mempool_blocks().await?;
   println!("{:?}\n", d);
 
-  let e = murray.blockchain.get_address_details(GetAddressParams {
-    address: "1F1tAaz5x1HUXrCNLbtMDqcw6o5GNn4xqX".to_string(),
-  })?;
+  let e = murray
+    .blockchain
+    .get_address_details(GetAddressParams {
+      address: "1F1tAaz5x1HUXrCNLbtMDqcw6o5GNn4xqX".to_string(),
+    })
+    .await?;
   println!("{:?}\n", e);
 
   let f = murray
     .blockchain
     .get_address_transactions(GetAddressParams {
       address: "3Brz916o2Ng2s6iYT9bgCpAxTRqd9b3GGW".to_string(),
-    })?;
+    })
+    .await?;
   println!("{:?}\n", f);
 
-  let g = murray.blockchain.get_address_utxos(GetAddressParams {
-    address: "3Brz916o2Ng2s6iYT9bgCpAxTRqd9b3GGW".to_string(),
-  })?;
+  let g = murray
+    .blockchain
+    .get_address_utxos(GetAddressParams {
+      address: "3Brz916o2Ng2s6iYT9bgCpAxTRqd9b3GGW".to_string(),
+    })
+    .await?;
   println!("{:?}\n", g);
 
-  let h = murray.blockchain.get_hashrate()?;
+  let h = murray.blockchain.get_hashrate().await?;
   println!("{:?}\n", h);
 
-  let i = murray.blockchain.get_health()?;
+  let i = murray.blockchain.get_health().await?;
   println!("{:?}\n", i);
 
-  let j = murray.blockchain.get_mempool()?;
+  let j = murray.blockchain.get_mempool().await?;
   println!("{:?}\n", j);
 
-  let k = murray.blockchain.get_transaction(GetTransactionParams {
-    txid: "15e10745f15593a899cef391191bdd3d7c12412cc4696b7bcb669d0feadc8521".to_string(),
-  })?;
+  let k = murray
+    .blockchain
+    .get_transaction(GetTransactionParams {
+      txid: "15e10745f15593a899cef391191bdd3d7c12412cc4696b7bcb669d0feadc8521"
+        .parse()
+        .unwrap(),
+    })
+    .await?;
   println!("{:?}\n", k);
 
   let tx_hex = "0200000001fd5b5fcd1cb066c27cfc9fda5428b9be850b81ac440ea51f1ddba2f987189ac1010000008a4730440220686a40e9d2dbffeab4ca1ff66341d06a17806767f12a1fc4f55740a7af24c6b5022049dd3c9a85ac6c51fecd5f4baff7782a518781bbdd94453c8383755e24ba755c01410436d554adf4a3eb03a317c77aa4020a7bba62999df633bba0ea8f83f48b9e01b0861d3b3c796840f982ee6b14c3c4b7ad04fcfcc3774f81bff9aaf52a15751fedfdffffff02416c00000000000017a914bc791b2afdfe1e1b5650864a9297b20d74c61f4787d71d0000000000001976a9140a59837ccd4df25adc31cdad39be6a8d97557ed688ac00000000".to_owned();
   let l = murray
     .blockchain
-    .post_transaction(PostTransactionParams { tx_hex })?;
+    .post_transaction(PostTransactionParams { tx_hex })
+    .await?;
   println!("{:?}\n", l);
 
   Ok(())
 }
 
-fn main() {
-  let _ = blockchain_example();
+#[tokio::main]
+async fn main() {
+  let _ = blockchain_example().await;
 }
@@ -0,0 +1,17 @@
+use murray_rs::{blocking::Murray, BlockRef, ConvertCurrencyParams, Currency};
+
+fn main() {
+  let murray = Murray::default();
+
+  let a = murray.blockchain.get_block(BlockRef::Height(500000));
+  println!("{:?}\n", a);
+
+  let b = murray.lightning.get_statistics();
+  println!("{:?}\n", b);
+
+  let c = murray.prices.convert_currency(ConvertCurrencyParams {
+    currency: Currency::BRL,
+    value: 100,
+  });
+  println!("{:?}\n", c);
+}
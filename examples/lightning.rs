@@ -1,25 +1,29 @@
-use murray_rs::{GetNodeDetailsParams, LightningError, Murray};
+use murray_rs::{GetNodeDetailsParams, Murray, MurrayError};
 
-fn lightning_example() -> std::result::Result<(), LightningError> {
+async fn lightning_example() -> std::result::Result<(), MurrayError> {
   let murray = Murray::default();
 
-  let a = murray.lightning.get_node_details(GetNodeDetailsParams {
-    public_key: "03864ef025fde8fb587d989186ce6a4a186895ee44a926bfc370e2c366597a3f8f".to_string(),
-  })?;
+  let a = murray
+    .lightning
+    .get_node_details(GetNodeDetailsParams {
+      public_key: "03864ef025fde8fb587d989186ce6a4a186895ee44a926bfc370e2c366597a3f8f".to_string(),
+    })
+    .await?;
   println!("{:?}", a);
 
-  let b = murray.lightning.get_statistics()?;
+  let b = murray.lightning.get_statistics().await?;
   println!("{:?}", b);
-  
-  let c = murray.lightning.get_top_nodes()?;
+
+  let c = murray.lightning.get_top_nodes().await?;
   println!("{:?}", c);
 
-  let d = murray.lightning.get_health()?;
+  let d = murray.lightning.get_health().await?;
   println!("{:?}", d);
 
   Ok(())
 }
 
-fn main() {
-  let _ = lightning_example();
-}
\ No newline at end of file
+#[tokio::main]
+async fn main() {
+  let _ = lightning_example().await;
+}
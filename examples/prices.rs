@@ -1,30 +1,40 @@
-use murray_rs::{ConvertCurrencyParams, Currency, GetTickerParams, Murray, PriceError, Symbol};
+use murray_rs::{ConvertCurrencyParams, Currency, GetTickerParams, Murray, MurrayError, Symbol};
 
-fn price_example() -> std::result::Result<(), PriceError> {
+async fn price_example() -> std::result::Result<(), MurrayError> {
   let murray = Murray::default();
 
-  let a = murray.prices.convert_currency(ConvertCurrencyParams {
-    currency: Currency::BRL,
-    value: 100,
-  })?;
+  let a = murray
+    .prices
+    .convert_currency(ConvertCurrencyParams {
+      currency: Currency::BRL,
+      value: 100,
+    })
+    .await?;
   println!("{:?}", a);
 
-  let b = murray.prices.get_ticker(GetTickerParams {
-    symbol: Symbol::BTCUSD,
-  })?;
+  let b = murray
+    .prices
+    .get_ticker(GetTickerParams {
+      symbol: Symbol::BTCUSD,
+    })
+    .await?;
   println!("{:?}", b);
 
-  let c = murray.prices.get_tickers(GetTickerParams {
-    symbol: Symbol::BTCUSD,
-  })?;
+  let c = murray
+    .prices
+    .get_tickers(GetTickerParams {
+      symbol: Symbol::BTCUSD,
+    })
+    .await?;
   println!("{:?}", c);
 
-  let d = murray.prices.get_health()?;
+  let d = murray.prices.get_health().await?;
   println!("{:?}", d);
 
   Ok(())
 }
 
-fn main() {
-  let _ = price_example();
+#[tokio::main]
+async fn main() {
+  let _ = price_example().await;
 }
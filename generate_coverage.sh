@@ -29,7 +29,7 @@ build_project() {
 
 run_tests() {
   echo "> Run tests";
-  cargo test --tests;
+  cargo test --tests --all-features;
 }
 
 run_gcov() {
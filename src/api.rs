@@ -0,0 +1,226 @@
+use async_trait::async_trait;
+
+use crate::{
+  error::Result, node_details::NodeData, statistics::Statistics, top_nodes::TopData, BlockRef,
+  Blockchain, ConvertCurrencyParams, ConvertCurrencyReturn, GetAddressDetailsResponse,
+  GetAddressParams, GetAddressTransactionsResponse, GetAddressUTXOResponse, GetBlock2TimeResponse,
+  GetBlockResponse, GetFeesMempoolBlocksResponse, GetFeesRecommendedResponse, GetHashrateResponse,
+  GetHealthResponse, GetMempoolResponse, GetNodeDetailsParams, GetTickerParams, GetTickerReturn,
+  GetTickersReturn, GetTransactionParams, GetTransactionResponse, Lightning, PostTransactionParams,
+  PostTransactionResponse, Prices,
+};
+
+/// The calls of the blockchain service, implemented by [`Blockchain`].
+///
+/// Depending on this trait rather than on [`Blockchain`] lets
+/// tests swap in another implementation, e.g. the `FakeBlockchain`
+/// of the `test-utils` feature:
+///
+/// ```
+/// use std::sync::Arc;
+/// use murray_rs::{BlockchainApi, Murray, Result};
+///
+/// struct FeeEstimator {
+///   blockchain: Arc<dyn BlockchainApi>,
+/// }
+///
+/// impl FeeEstimator {
+///   async fn fastest_fee(&self) -> Result<u64> {
+///     Ok(self.blockchain.get_fees_recommended().await?.fees_recommended.fastest_fee)
+///   }
+/// }
+///
+/// let estimator = FeeEstimator {
+///   blockchain: Arc::new(Murray::default().blockchain),
+/// };
+/// ```
+///
+#[async_trait]
+pub trait BlockchainApi: Send + Sync {
+  /// See [`Blockchain::get_block`].
+  async fn get_block(&self, block: BlockRef) -> Result<GetBlockResponse>;
+
+  /// See [`Blockchain::get_block2time`].
+  async fn get_block2time(&self, block: BlockRef) -> Result<GetBlock2TimeResponse>;
+
+  /// See [`Blockchain::get_fees_recommended`].
+  async fn get_fees_recommended(&self) -> Result<GetFeesRecommendedResponse>;
+
+  /// See [`Blockchain::get_fees_mempool_blocks`].
+  async fn get_fees_mempool_blocks(&self) -> Result<Vec<GetFeesMempoolBlocksResponse>>;
+
+  /// See [`Blockchain::get_address_details`].
+  async fn get_address_details(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<GetAddressDetailsResponse>;
+
+  /// See [`Blockchain::get_address_transactions`].
+  async fn get_address_transactions(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Vec<GetAddressTransactionsResponse>>;
+
+  /// See [`Blockchain::get_address_utxos`].
+  async fn get_address_utxos(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Vec<GetAddressUTXOResponse>>;
+
+  /// See [`Blockchain::get_hashrate`].
+  async fn get_hashrate(&self) -> Result<GetHashrateResponse>;
+
+  /// See [`Blockchain::get_health`].
+  async fn get_health(&self) -> Result<GetHealthResponse>;
+
+  /// See [`Blockchain::get_mempool`].
+  async fn get_mempool(&self) -> Result<GetMempoolResponse>;
+
+  /// See [`Blockchain::get_transaction`].
+  async fn get_transaction(&self, params: GetTransactionParams) -> Result<GetTransactionResponse>;
+
+  /// See [`Blockchain::post_transaction`].
+  async fn post_transaction(
+    &self,
+    params: PostTransactionParams,
+  ) -> Result<PostTransactionResponse>;
+}
+
+/// The calls of the lightning service, implemented by [`Lightning`].
+///
+/// See [`BlockchainApi`] for how to depend on it.
+///
+#[async_trait]
+pub trait LightningApi: Send + Sync {
+  /// See [`Lightning::get_node_details`].
+  async fn get_node_details(&self, params: GetNodeDetailsParams) -> Result<NodeData>;
+
+  /// See [`Lightning::get_statistics`].
+  async fn get_statistics(&self) -> Result<Statistics>;
+
+  /// See [`Lightning::get_top_nodes`].
+  async fn get_top_nodes(&self) -> Result<TopData>;
+
+  /// See [`Lightning::get_health`].
+  async fn get_health(&self) -> Result<GetHealthResponse>;
+}
+
+/// The calls of the prices service, implemented by [`Prices`].
+///
+/// See [`BlockchainApi`] for how to depend on it.
+///
+#[async_trait]
+pub trait PricesApi: Send + Sync {
+  /// See [`Prices::convert_currency`].
+  async fn convert_currency(&self, params: ConvertCurrencyParams) -> Result<ConvertCurrencyReturn>;
+
+  /// See [`Prices::get_ticker`].
+  async fn get_ticker(&self, params: GetTickerParams) -> Result<GetTickerReturn>;
+
+  /// See [`Prices::get_tickers`].
+  async fn get_tickers(&self, params: GetTickerParams) -> Result<GetTickersReturn>;
+
+  /// See [`Prices::get_health`].
+  async fn get_health(&self) -> Result<GetHealthResponse>;
+}
+
+#[async_trait]
+impl BlockchainApi for Blockchain {
+  async fn get_block(&self, block: BlockRef) -> Result<GetBlockResponse> {
+    Blockchain::get_block(self, block).await
+  }
+
+  async fn get_block2time(&self, block: BlockRef) -> Result<GetBlock2TimeResponse> {
+    Blockchain::get_block2time(self, block).await
+  }
+
+  async fn get_fees_recommended(&self) -> Result<GetFeesRecommendedResponse> {
+    Blockchain::get_fees_recommended(self).await
+  }
+
+  async fn get_fees_mempool_blocks(&self) -> Result<Vec<GetFeesMempoolBlocksResponse>> {
+    Blockchain::get_fees_mempool_blocks(self).await
+  }
+
+  async fn get_address_details(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<GetAddressDetailsResponse> {
+    Blockchain::get_address_details(self, params).await
+  }
+
+  async fn get_address_transactions(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Vec<GetAddressTransactionsResponse>> {
+    Blockchain::get_address_transactions(self, params).await
+  }
+
+  async fn get_address_utxos(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Vec<GetAddressUTXOResponse>> {
+    Blockchain::get_address_utxos(self, params).await
+  }
+
+  async fn get_hashrate(&self) -> Result<GetHashrateResponse> {
+    Blockchain::get_hashrate(self).await
+  }
+
+  async fn get_health(&self) -> Result<GetHealthResponse> {
+    Blockchain::get_health(self).await
+  }
+
+  async fn get_mempool(&self) -> Result<GetMempoolResponse> {
+    Blockchain::get_mempool(self).await
+  }
+
+  async fn get_transaction(&self, params: GetTransactionParams) -> Result<GetTransactionResponse> {
+    Blockchain::get_transaction(self, params).await
+  }
+
+  async fn post_transaction(
+    &self,
+    params: PostTransactionParams,
+  ) -> Result<PostTransactionResponse> {
+    Blockchain::post_transaction(self, params).await
+  }
+}
+
+#[async_trait]
+impl LightningApi for Lightning {
+  async fn get_node_details(&self, params: GetNodeDetailsParams) -> Result<NodeData> {
+    Lightning::get_node_details(self, params).await
+  }
+
+  async fn get_statistics(&self) -> Result<Statistics> {
+    Lightning::get_statistics(self).await
+  }
+
+  async fn get_top_nodes(&self) -> Result<TopData> {
+    Lightning::get_top_nodes(self).await
+  }
+
+  async fn get_health(&self) -> Result<GetHealthResponse> {
+    Lightning::get_health(self).await
+  }
+}
+
+#[async_trait]
+impl PricesApi for Prices {
+  async fn convert_currency(&self, params: ConvertCurrencyParams) -> Result<ConvertCurrencyReturn> {
+    Prices::convert_currency(self, params).await
+  }
+
+  async fn get_ticker(&self, params: GetTickerParams) -> Result<GetTickerReturn> {
+    Prices::get_ticker(self, params).await
+  }
+
+  async fn get_tickers(&self, params: GetTickerParams) -> Result<GetTickersReturn> {
+    Prices::get_tickers(self, params).await
+  }
+
+  async fn get_health(&self) -> Result<GetHealthResponse> {
+    Prices::get_health(self).await
+  }
+}
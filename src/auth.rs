@@ -0,0 +1,195 @@
+use std::{
+  fmt,
+  future::Future,
+  pin::Pin,
+  sync::Arc,
+  time::{Duration, Instant},
+};
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, InvalidHeaderName, AUTHORIZATION};
+
+use crate::middleware::BoxError;
+
+/// Tokens are refreshed this long before they expire,
+/// so that they don't expire on their way to the service.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(10);
+
+type TokenFuture = Pin<Box<dyn Future<Output = Result<Token, BoxError>> + Send>>;
+type TokenProvider = Arc<dyn Fn() -> TokenFuture + Send + Sync>;
+
+/// Credentials sent with every request to a service, e.g. when
+/// self-hosted services sit behind an authenticating gateway.
+///
+/// Credentials are marked as sensitive and left out of the
+/// `Debug` output, and they are scrubbed from the messages
+/// and bodies of the errors returned by the services.
+///
+/// ```
+/// use std::time::Duration;
+/// use murray_rs::{Auth, Murray, Service, Token};
+///
+/// let murray = Murray::builder()
+///   .auth(Service::Blockchain, Auth::bearer("some-token"))
+///   .auth(Service::Prices, Auth::api_key("x-api-key", "some-key").unwrap())
+///   .auth(
+///     Service::Lightning,
+///     Auth::token_provider(|| async {
+///       // e.g. an OAuth client credentials grant
+///       Ok(Token::new("some-token").expires_in(Duration::from_secs(3600)))
+///     }),
+///   )
+///   .build()
+///   .unwrap();
+/// ```
+///
+#[derive(Clone)]
+pub struct Auth {
+  kind: AuthKind,
+}
+
+#[derive(Clone)]
+enum AuthKind {
+  Bearer(String),
+  ApiKey {
+    header: HeaderName,
+    key: String,
+  },
+  Provider {
+    provider: TokenProvider,
+    token: Arc<tokio::sync::Mutex<Option<Token>>>,
+  },
+}
+
+impl Auth {
+  /// Sends `Authorization: Bearer <token>`.
+  ///
+  pub fn bearer(token: impl Into<String>) -> Self {
+    Self {
+      kind: AuthKind::Bearer(token.into()),
+    }
+  }
+
+  /// Sends the key in the `header` header, e.g. `x-api-key`.
+  ///
+  pub fn api_key(header: &str, key: impl Into<String>) -> Result<Self, InvalidHeaderName> {
+    Ok(Self {
+      kind: AuthKind::ApiKey {
+        header: HeaderName::try_from(header)?,
+        key: key.into(),
+      },
+    })
+  }
+
+  /// Sends `Authorization: Bearer <token>`, with the token returned
+  /// by `provider`. It is called again once the token expired, or
+  /// when the service answers `401 Unauthorized`, in which case
+  /// the request is sent once more with the new token.
+  ///
+  pub fn token_provider<F, Fut>(provider: F) -> Self
+  where
+    F: Fn() -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<Token, BoxError>> + Send + 'static,
+  {
+    Self {
+      kind: AuthKind::Provider {
+        provider: Arc::new(move || Box::pin(provider())),
+        token: Arc::new(tokio::sync::Mutex::new(None)),
+      },
+    }
+  }
+
+  /// Adds the credentials to the `headers` of a request.
+  pub(crate) async fn apply(&self, headers: &mut HeaderMap) -> Result<(), BoxError> {
+    let (name, value) = match &self.kind {
+      AuthKind::Bearer(token) => (AUTHORIZATION, format!("Bearer {}", token)),
+      AuthKind::ApiKey { header, key } => (header.clone(), key.clone()),
+      AuthKind::Provider { provider, token } => {
+        // held while refreshing, so that concurrent requests share the new token
+        let mut cached = token.lock().await;
+        let token = match cached.take().filter(|token| !token.is_expired()) {
+          Some(token) => token,
+          None => provider().await?,
+        };
+        let value = format!("Bearer {}", token.value);
+        *cached = Some(token);
+        (AUTHORIZATION, value)
+      }
+    };
+
+    let mut value = HeaderValue::try_from(value)?;
+    value.set_sensitive(true);
+    headers.insert(name, value);
+    Ok(())
+  }
+
+  /// Drops the token of a token provider, so that the next
+  /// request gets a new one. Returns whether there was one.
+  pub(crate) async fn invalidate(&self) -> bool {
+    match &self.kind {
+      AuthKind::Provider { token, .. } => token.lock().await.take().is_some(),
+      _ => false,
+    }
+  }
+
+  /// The credential that must not show up in errors.
+  pub(crate) async fn secret(&self) -> Option<String> {
+    match &self.kind {
+      AuthKind::Bearer(token) => Some(token.clone()),
+      AuthKind::ApiKey { key, .. } => Some(key.clone()),
+      AuthKind::Provider { token, .. } => {
+        token.lock().await.as_ref().map(|token| token.value.clone())
+      }
+    }
+  }
+}
+
+impl fmt::Debug for Auth {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.kind {
+      AuthKind::Bearer(_) => f.write_str("Auth::Bearer(<redacted>)"),
+      AuthKind::ApiKey { header, .. } => write!(f, "Auth::ApiKey({}: <redacted>)", header),
+      AuthKind::Provider { .. } => f.write_str("Auth::TokenProvider"),
+    }
+  }
+}
+
+/// A bearer token returned by the provider of an [`Auth::token_provider`].
+#[derive(Clone)]
+pub struct Token {
+  value: String,
+  expires_at: Option<Instant>,
+}
+
+impl Token {
+  /// A token which doesn't expire, until the service
+  /// answers `401 Unauthorized`.
+  ///
+  pub fn new(value: impl Into<String>) -> Self {
+    Self {
+      value: value.into(),
+      expires_at: None,
+    }
+  }
+
+  /// Gets a new token once this one is about to expire.
+  ///
+  pub fn expires_in(mut self, expires_in: Duration) -> Self {
+    self.expires_at = Some(Instant::now() + expires_in);
+    self
+  }
+
+  fn is_expired(&self) -> bool {
+    self
+      .expires_at
+      .is_some_and(|at| Instant::now() + EXPIRY_MARGIN >= at)
+  }
+}
+
+impl fmt::Debug for Token {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("Token")
+      .field("value", &"<redacted>")
+      .field("expires_at", &self.expires_at)
+      .finish()
+  }
+}
@@ -0,0 +1,95 @@
+use std::future::Future;
+
+use futures_util::{stream, StreamExt};
+
+use crate::error::Result;
+
+use super::{
+  types::{
+    BlockRef, GetAddressDetailsResponse, GetAddressParams, GetBlockResponse, GetTransactionParams,
+    GetTransactionResponse,
+  },
+  Blockchain,
+};
+
+impl Blockchain {
+  /// Gets many blocks, with at most `concurrency` calls in flight.
+  /// See [`Blockchain::get_transactions`].
+  ///
+  pub async fn get_blocks(
+    &self,
+    blocks: impl IntoIterator<Item = BlockRef>,
+    concurrency: usize,
+  ) -> Vec<Result<GetBlockResponse>> {
+    batch(blocks, concurrency, |block| self.get_block(block)).await
+  }
+
+  /// Gets the details of many addresses, with at most `concurrency`
+  /// calls in flight. See [`Blockchain::get_transactions`].
+  ///
+  pub async fn get_addresses_details(
+    &self,
+    params: impl IntoIterator<Item = GetAddressParams>,
+    concurrency: usize,
+  ) -> Vec<Result<GetAddressDetailsResponse>> {
+    batch(params, concurrency, |params| {
+      self.get_address_details(params)
+    })
+    .await
+  }
+
+  /// Gets many transactions, with at most `concurrency` calls in
+  /// flight (at least one). Each call has its own result, in the
+  /// order of `params`, so that one failing call doesn't fail the
+  /// others.
+  ///
+  /// ```no_run
+  /// # async fn run() {
+  /// use murray_rs::{GetTransactionParams, Murray};
+  ///
+  /// let murray = Murray::default();
+  /// let txids = ["15e10745f15593a899cef391191bdd3d7c12412cc4696b7bcb669d0feadc8521"];
+  ///
+  /// let results = murray
+  ///   .blockchain
+  ///   .get_transactions(
+  ///     txids.map(|txid| GetTransactionParams { txid: txid.parse().unwrap() }),
+  ///     8,
+  ///   )
+  ///   .await;
+  ///
+  /// for (txid, result) in txids.iter().zip(results) {
+  ///   match result {
+  ///     Ok(response) => println!("{}: {} vbytes", txid, response.transaction.weight / 4),
+  ///     Err(e) => println!("{}: {}", txid, e),
+  ///   }
+  /// }
+  /// # }
+  /// ```
+  ///
+  pub async fn get_transactions(
+    &self,
+    params: impl IntoIterator<Item = GetTransactionParams>,
+    concurrency: usize,
+  ) -> Vec<Result<GetTransactionResponse>> {
+    batch(params, concurrency, |params| self.get_transaction(params)).await
+  }
+}
+
+/// Makes a `call` with each of `params`, with at most
+/// `concurrency` in flight, and returns their results in order.
+async fn batch<P, T, F, Fut>(
+  params: impl IntoIterator<Item = P>,
+  concurrency: usize,
+  call: F,
+) -> Vec<Result<T>>
+where
+  F: FnMut(P) -> Fut,
+  Fut: Future<Output = Result<T>>,
+{
+  stream::iter(params)
+    .map(call)
+    .buffered(concurrency.max(1))
+    .collect()
+    .await
+}
@@ -0,0 +1,231 @@
+use std::{
+  path::PathBuf,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::transport::Request;
+
+use super::{
+  types::{
+    BlockHash, BlockRef, GetBlockResponse, GetBlockResponseJsonData, GetTransactionResponse, Txid,
+  },
+  Blockchain,
+};
+
+/// How long the tip height is trusted before being fetched again.
+/// A stale tip only underestimates confirmations, so it is safe.
+const TIP_TTL: Duration = Duration::from_secs(60);
+
+/// Configuration of the persistent cache of blocks and
+/// confirmed transactions, a directory of JSON files.
+///
+/// A block or transaction is only stored once it is buried
+/// under `min_confirmations` blocks (default 6), so that a
+/// reorg can't make it stale. Blocks are looked up by hash or
+/// height, transactions by txid; the tip is never stored.
+///
+/// ```no_run
+/// use murray_rs::{DiskCacheConfig, Murray};
+///
+/// let murray = Murray::builder()
+///   .disk_cache(DiskCacheConfig::new("/var/cache/my-app/murray").min_confirmations(100))
+///   .build()
+///   .unwrap();
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct DiskCacheConfig {
+  dir: PathBuf,
+  min_confirmations: u32,
+}
+
+impl DiskCacheConfig {
+  pub fn new(dir: impl Into<PathBuf>) -> Self {
+    Self {
+      dir: dir.into(),
+      min_confirmations: 6,
+    }
+  }
+
+  /// How deep a block or a transaction must be before it is stored.
+  /// `1` stores them as soon as they are mined.
+  ///
+  pub fn min_confirmations(mut self, min_confirmations: u32) -> Self {
+    self.min_confirmations = min_confirmations.max(1);
+    self
+  }
+}
+
+/// The files backing a [`DiskCacheConfig`], shared by all
+/// clones of a [`Blockchain`].
+pub(crate) struct DiskCache {
+  config: DiskCacheConfig,
+  tip: Mutex<Option<(u32, Instant)>>,
+}
+
+/// What is stored in a file of the cache.
+enum Entry {
+  BlockByHash(BlockHash),
+  BlockByHeight(u32),
+  Transaction(Txid),
+}
+
+impl DiskCache {
+  pub fn new(config: DiskCacheConfig) -> Arc<Self> {
+    Arc::new(Self {
+      config,
+      tip: Mutex::new(None),
+    })
+  }
+
+  /// Path of the file of `entry`.
+  fn path(&self, entry: &Entry) -> PathBuf {
+    let (dir, file) = match entry {
+      Entry::BlockByHash(hash) => ("blocks", format!("{}.json", hash)),
+      Entry::BlockByHeight(height) => ("blocks", format!("height-{}.json", height)),
+      Entry::Transaction(txid) => ("transactions", format!("{}.json", txid)),
+    };
+
+    self.config.dir.join(dir).join(file)
+  }
+
+  /// Missing, unreadable or corrupted files are all cache misses.
+  async fn read<T: DeserializeOwned>(&self, entry: &Entry) -> Option<T> {
+    let path = self.path(entry);
+    let bytes = tokio::fs::read(path).await.ok()?;
+    serde_json::from_slice(&bytes).ok()
+  }
+
+  /// Best effort: failing to store an entry doesn't fail the call.
+  async fn write<T: Serialize>(&self, entry: &Entry, value: &T) {
+    let path = self.path(entry);
+    let Ok(bytes) = serde_json::to_vec(value) else {
+      return;
+    };
+    let Some(dir) = path.parent() else { return };
+
+    // write then rename, so that readers never see a partial file
+    let tmp = path.with_extension(format!("tmp-{}", fastrand::u64(..)));
+    let written = async {
+      tokio::fs::create_dir_all(dir).await?;
+      tokio::fs::write(&tmp, bytes).await?;
+      tokio::fs::rename(&tmp, &path).await
+    };
+    if written.await.is_err() {
+      let _ = tokio::fs::remove_file(&tmp).await;
+    }
+  }
+
+  fn is_deep_enough(&self, height: u32, tip: u32) -> bool {
+    tip.saturating_sub(height).saturating_add(1) >= self.config.min_confirmations
+  }
+
+  fn tip(&self) -> Option<u32> {
+    let tip = self.tip.lock().unwrap_or_else(|e| e.into_inner());
+    tip
+      .filter(|(_, at)| at.elapsed() < TIP_TTL)
+      .map(|(height, _)| height)
+  }
+
+  fn set_tip(&self, height: u32) {
+    *self.tip.lock().unwrap_or_else(|e| e.into_inner()) = Some((height, Instant::now()));
+  }
+}
+
+impl Blockchain {
+  /// The disk cache to serve calls from, none when they bypass
+  /// the caches. Their fresh responses are still stored.
+  fn readable_disk_cache(&self) -> Option<&DiskCache> {
+    let disk_cache = self.disk_cache.as_deref()?;
+    (!self.transport.bypasses_cache()).then_some(disk_cache)
+  }
+
+  pub(super) async fn stored_block(&self, block: BlockRef) -> Option<GetBlockResponse> {
+    let disk_cache = self.readable_disk_cache()?;
+    let entry = match block {
+      BlockRef::Hash(hash) => Entry::BlockByHash(hash),
+      BlockRef::Height(height) => Entry::BlockByHeight(height),
+      BlockRef::Tip => return None,
+    };
+
+    disk_cache.read(&entry).await
+  }
+
+  /// Stores `block`, fetched as `block_ref`.
+  pub(super) async fn store_block(&self, block_ref: BlockRef, block: &GetBlockResponse) {
+    let Some(disk_cache) = self.disk_cache.as_ref() else {
+      return;
+    };
+    let height = block.block.height;
+    // no need to fetch the tip again to learn its height
+    if block_ref == BlockRef::Tip {
+      disk_cache.set_tip(height);
+    }
+    if !self.is_deep_enough(disk_cache, height).await {
+      return;
+    }
+
+    disk_cache
+      .write(&Entry::BlockByHash(block.block.id), block)
+      .await;
+    disk_cache.write(&Entry::BlockByHeight(height), block).await;
+  }
+
+  pub(super) async fn stored_transaction(&self, txid: Txid) -> Option<GetTransactionResponse> {
+    let disk_cache = self.readable_disk_cache()?;
+    disk_cache.read(&Entry::Transaction(txid)).await
+  }
+
+  pub(super) async fn store_transaction(&self, transaction: &GetTransactionResponse) {
+    let Some(disk_cache) = self.disk_cache.as_ref() else {
+      return;
+    };
+    let status = &transaction.transaction.status;
+    let height = match (status.confirmed, status.block_height) {
+      (true, Some(height)) => height,
+      _ => return,
+    };
+    if !self.is_deep_enough(disk_cache, height).await {
+      return;
+    }
+
+    disk_cache
+      .write(
+        &Entry::Transaction(transaction.transaction.txid),
+        transaction,
+      )
+      .await;
+  }
+
+  /// Whether a block at `height` has enough confirmations,
+  /// fetching the tip height when it is needed and unknown.
+  async fn is_deep_enough(&self, disk_cache: &DiskCache, height: u32) -> bool {
+    if disk_cache.config.min_confirmations <= 1 {
+      return true;
+    }
+
+    let tip = match disk_cache.tip() {
+      Some(tip) => tip,
+      None => {
+        let request = Request::get("/block");
+        match self
+          .transport
+          .send::<GetBlockResponseJsonData>(request)
+          .await
+        {
+          Ok(tip) => {
+            let height = tip.data().data.block.height;
+            disk_cache.set_tip(height);
+            height
+          }
+          Err(_) => return false,
+        }
+      }
+    };
+
+    disk_cache.is_deep_enough(height, tip)
+  }
+}
@@ -1,398 +1,587 @@
+mod batch;
+#[cfg(feature = "disk-cache")]
+pub(crate) mod disk_cache;
 pub mod types;
-use std::result;
 
 use reqwest::Client;
 
-use crate::{GetHealthResponse, GetHealthResponseJsonData};
+use crate::{
+  error::Result,
+  middleware,
+  transport::{Request, Transport},
+  CircuitState, GetHealthResponse, GetHealthResponseJsonData, Partial, RequestOptions, Response,
+  Service, WithMeta,
+};
 
 use self::types::{
-  GetAddressDetailsResponse, GetAddressDetailsResponseJsonData, GetAddressParams,
+  BlockRef, GetAddressDetailsResponse, GetAddressDetailsResponseJsonData, GetAddressParams,
   GetAddressTransactionsResponse, GetAddressTransactionsResponseJsonData, GetAddressUTXOResponse,
   GetAddressUTXOResponseJsonData, GetBlock2TimeResponse, GetBlock2TimeResponseJsonData,
-  GetBlockParams, GetBlockResponse, GetBlockResponseJsonData, GetFeesMempoolBlocksResponse,
+  GetBlockResponse, GetBlockResponseJsonData, GetFeesMempoolBlocksResponse,
   GetFeesMempoolBlocksResponseJsonData, GetFeesRecommendedResponse,
   GetFeesRecommendedResponseJsonData, GetHashrateResponse, GetHashrateResponseJsonData,
   GetMempoolResponse, GetMempoolResponseJsonData, GetTransactionParams, GetTransactionResponse,
-  GetTransactionResponseJsonData, PostTransactionParams, PostTransactionResponse,
-  PostTransactionResponseJsonData, BlockchainError,
+  GetTransactionResponseJsonData, ListJsonData, PostTransactionParams, PostTransactionResponse,
+  PostTransactionResponseJsonData,
 };
 
-type Result<T> = result::Result<T, BlockchainError>;
-
+/// Async client for the blockchain service.
+///
+/// Cloning is cheap: clones share the same underlying
+/// connection pool.
+///
+#[derive(Clone)]
 pub struct Blockchain {
-  base_url: String,
-  client: Client,
+  transport: Transport,
+  #[cfg(feature = "disk-cache")]
+  disk_cache: Option<std::sync::Arc<disk_cache::DiskCache>>,
 }
 
 impl Blockchain {
   pub fn new(url: String) -> Self {
-    let client = reqwest::Client::new();
+    Self::with_client(url, Client::new())
+  }
+
+  /// Creates a new [`Blockchain`] reusing an existing [`Client`],
+  /// so its connection pool can be shared with other services.
+  ///
+  pub fn with_client(url: String, client: Client) -> Self {
+    Self::from_transport(Transport::new(
+      Service::Blockchain,
+      url,
+      middleware::stack(client, &[]),
+    ))
+  }
+
+  pub(crate) fn from_transport(transport: Transport) -> Self {
     Self {
-      base_url: url,
-      client,
+      transport,
+      #[cfg(feature = "disk-cache")]
+      disk_cache: None,
     }
   }
 
+  pub(crate) fn transport(&self) -> &Transport {
+    &self.transport
+  }
+
+  #[cfg(feature = "disk-cache")]
+  pub(crate) fn with_disk_cache(
+    mut self,
+    disk_cache: Option<std::sync::Arc<disk_cache::DiskCache>>,
+  ) -> Self {
+    self.disk_cache = disk_cache;
+    self
+  }
+
   /// Change the base url on the fly for the [`Blockchain`] calls.
-  /// 
+  ///
   pub fn set_base_url(&mut self, base_url: String) {
-    self.base_url = base_url;
+    self.transport.set_base_url(base_url);
   }
 
-  /// Get details about a specific block determined by
-  /// its height or hash.
-  /// If none is passed, it will return information of the
-  /// last block (block tip).
-  /// 
-  #[tokio::main]
-  pub async fn get_block(
-    &self,
-    GetBlockParams { hash, height }: GetBlockParams,
-  ) -> Result<GetBlockResponse> {
-    let url = format!("{}/block", self.base_url);
+  /// State of the circuit breaker of the [`Blockchain`] service, `None`
+  /// unless one was set with [`MurrayBuilder::circuit_breaker`](crate::MurrayBuilder::circuit_breaker).
+  ///
+  pub fn circuit_state(&self) -> Option<CircuitState> {
+    self.transport.circuit_state()
+  }
 
-    let mut params = vec![];
+  /// A view of the [`Blockchain`] service whose calls return
+  /// the metadata of their HTTP response along with its data.
+  ///
+  pub fn with_meta(&self) -> WithMeta<'_, Self> {
+    WithMeta { inner: self }
+  }
 
-    if let Some(s) = hash {
-      params.push(("hash", s))
-    }
+  /// A copy of the [`Blockchain`] service whose calls are made with
+  /// `options`, e.g. a timeout or another base URL.
+  ///
+  pub fn with_options(&self, options: RequestOptions) -> Self {
+    // the disk cache only holds what the usual base URLs served
+    #[cfg(feature = "disk-cache")]
+    let disk_cache = match options.base_url {
+      Some(_) => None,
+      None => self.disk_cache.clone(),
+    };
 
-    if let Some(s) = height {
-      params.push(("height", s.to_string()))
+    Self {
+      transport: self.transport.clone().with_options(options),
+      #[cfg(feature = "disk-cache")]
+      disk_cache,
     }
+  }
 
-    let url = match reqwest::Url::parse_with_params(&url, &params) {
-      Ok(url) => url,
-      Err(e) => return Err(BlockchainError::InvalidURLParams(e.to_string())),
-    };
+  /// Get details about a specific block determined by
+  /// its height or hash, or about the last block
+  /// (block tip) with [`BlockRef::Tip`].
+  ///
+  pub async fn get_block(&self, block: BlockRef) -> Result<GetBlockResponse> {
+    self
+      .with_meta()
+      .get_block(block)
+      .await
+      .map(Response::into_data)
+  }
 
-    let client = self.client.get(url).header("Accept", "application/json");
+  /// Get block time.
+  ///
+  pub async fn get_block2time(&self, block: BlockRef) -> Result<GetBlock2TimeResponse> {
+    self
+      .with_meta()
+      .get_block2time(block)
+      .await
+      .map(Response::into_data)
+  }
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+  /// Get currently suggested fees for new transactions.
+  ///
+  pub async fn get_fees_recommended(&self) -> Result<GetFeesRecommendedResponse> {
+    self
+      .with_meta()
+      .get_fees_recommended()
+      .await
+      .map(Response::into_data)
+  }
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetBlockResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+  /// Get current mempool as projected blocks.
+  ///
+  pub async fn get_fees_mempool_blocks(&self) -> Result<Vec<GetFeesMempoolBlocksResponse>> {
+    self
+      .with_meta()
+      .get_fees_mempool_blocks()
+      .await
+      .map(Response::into_data)
+  }
 
-    Ok(data)
+  /// Same as [`Blockchain::get_fees_mempool_blocks`], decoding the
+  /// blocks one by one. See [`Partial`].
+  ///
+  pub async fn get_fees_mempool_blocks_lenient(
+    &self,
+  ) -> Result<Partial<GetFeesMempoolBlocksResponse>> {
+    self
+      .with_meta()
+      .get_fees_mempool_blocks_lenient()
+      .await
+      .map(Response::into_data)
   }
 
-  /// Get block time.
-  /// 
-  #[tokio::main]
-  pub async fn get_block2time(
+  /// Get details about an address.
+  /// Check [`GetAddressDetailsResponse`](self::types::GetAddressDetailsResponse)
+  /// for available data.
+  ///
+  pub async fn get_address_details(
     &self,
-    GetBlockParams { hash, height }: GetBlockParams,
-  ) -> Result<GetBlock2TimeResponse> {
-    let url = format!("{}/block2time", self.base_url);
+    params: GetAddressParams,
+  ) -> Result<GetAddressDetailsResponse> {
+    self
+      .with_meta()
+      .get_address_details(params)
+      .await
+      .map(Response::into_data)
+  }
 
-    let mut params = vec![];
+  /// Get transaction history for the specified address/scripthash,
+  /// sorted with newest first.
+  /// Returns up to 50 mempool transactions plus the first 25 confirmed transactions.
+  ///
+  pub async fn get_address_transactions(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Vec<GetAddressTransactionsResponse>> {
+    self
+      .with_meta()
+      .get_address_transactions(params)
+      .await
+      .map(Response::into_data)
+  }
 
-    if let Some(s) = hash {
-      params.push(("hash", s))
-    }
+  /// Same as [`Blockchain::get_address_transactions`], decoding
+  /// the transactions one by one. See [`Partial`].
+  ///
+  pub async fn get_address_transactions_lenient(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Partial<GetAddressTransactionsResponse>> {
+    self
+      .with_meta()
+      .get_address_transactions_lenient(params)
+      .await
+      .map(Response::into_data)
+  }
 
-    if let Some(s) = height {
-      params.push(("height", s.to_string()))
-    }
+  /// Get the list of unspent transaction outputs associated with the address/scripthash.
+  ///
+  pub async fn get_address_utxos(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Vec<GetAddressUTXOResponse>> {
+    self
+      .with_meta()
+      .get_address_utxos(params)
+      .await
+      .map(Response::into_data)
+  }
 
-    let url = match reqwest::Url::parse_with_params(&url, &params) {
-      Ok(url) => url,
-      Err(e) => return Err(BlockchainError::InvalidURLParams(e.to_string())),
-    };
+  /// Same as [`Blockchain::get_address_utxos`], decoding
+  /// the outputs one by one. See [`Partial`].
+  ///
+  pub async fn get_address_utxos_lenient(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Partial<GetAddressUTXOResponse>> {
+    self
+      .with_meta()
+      .get_address_utxos_lenient(params)
+      .await
+      .map(Response::into_data)
+  }
 
-    let client = self.client.get(url).header("Accept", "application/json");
+  /// Get network-wide hashrate and difficulty figures.
+  ///
+  pub async fn get_hashrate(&self) -> Result<GetHashrateResponse> {
+    self
+      .with_meta()
+      .get_hashrate()
+      .await
+      .map(Response::into_data)
+  }
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+  /// Get simple information regarding the health
+  /// of the blockchain service.
+  ///
+  /// More info at [service-blockchain](https://github.com/murray-rothbot/service-blockchain).
+  ///
+  pub async fn get_health(&self) -> Result<GetHealthResponse> {
+    self.with_meta().get_health().await.map(Response::into_data)
+  }
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetBlock2TimeResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+  /// Get current mempool backlog statistics.
+  ///
+  pub async fn get_mempool(&self) -> Result<GetMempoolResponse> {
+    self
+      .with_meta()
+      .get_mempool()
+      .await
+      .map(Response::into_data)
+  }
 
-    Ok(data)
+  /// Get details about a transaction.
+  ///
+  pub async fn get_transaction(
+    &self,
+    params: GetTransactionParams,
+  ) -> Result<GetTransactionResponse> {
+    self
+      .with_meta()
+      .get_transaction(params)
+      .await
+      .map(Response::into_data)
   }
 
-  /// Get currently suggested fees for new transactions.
-  /// 
-  #[tokio::main]
-  pub async fn get_fees_recommended(&self) -> Result<GetFeesRecommendedResponse> {
-    let url = format!("{}/fees/recommended", self.base_url);
+  /// Broadcast a raw transaction to the network.
+  /// The transaction should be provided as hex in the request body.
+  /// The txid will be returned on success.
+  ///
+  /// It is not retried on failure unless the [`RetryPolicy`](crate::RetryPolicy)
+  /// opts in with [`retry_post`](crate::RetryPolicy::retry_post).
+  ///
+  pub async fn post_transaction(
+    &self,
+    params: PostTransactionParams,
+  ) -> Result<PostTransactionResponse> {
+    self
+      .with_meta()
+      .post_transaction(params)
+      .await
+      .map(Response::into_data)
+  }
+}
 
-    let client = self.client.get(url).header("Accept", "application/json");
+impl WithMeta<'_, Blockchain> {
+  /// Same as [`Blockchain::get_block`], with the metadata of the response.
+  ///
+  pub async fn get_block(&self, block: BlockRef) -> Result<Response<GetBlockResponse>> {
+    #[cfg(feature = "disk-cache")]
+    let stored = self.inner.stored_block(block).await;
+
+    let mut request = Request::get("/block");
+    // unlike the tip, a block found by its height or hash doesn't change
+    if block != BlockRef::Tip {
+      request = request.immutable();
+    }
+    let request = request.query(block.query());
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+    #[cfg(feature = "disk-cache")]
+    if let Some(block) = stored {
+      return self.inner.transport.stored(&request, block);
+    }
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetFeesRecommendedResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetBlockResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
+
+    #[cfg(feature = "disk-cache")]
+    self.inner.store_block(block, response.data()).await;
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get current mempool as projected blocks.
-  /// 
-  #[tokio::main]
-  pub async fn get_fees_mempool_blocks(&self) -> Result<Vec<GetFeesMempoolBlocksResponse>> {
-    let url = format!("{}/fees/mempool-blocks", self.base_url);
+  /// Same as [`Blockchain::get_block2time`], with the metadata of the response.
+  ///
+  pub async fn get_block2time(&self, block: BlockRef) -> Result<Response<GetBlock2TimeResponse>> {
+    let mut request = Request::get("/block2time");
+    // unlike the tip, a block found by its height or hash doesn't change
+    if block != BlockRef::Tip {
+      request = request.immutable();
+    }
+    let request = request.query(block.query());
 
-    let client = self.client.get(url).header("Accept", "application/json");
+    let response = self
+      .inner
+      .transport
+      .send::<GetBlock2TimeResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+    Ok(response)
+  }
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetFeesMempoolBlocksResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+  /// Same as [`Blockchain::get_fees_recommended`], with the metadata of the response.
+  ///
+  pub async fn get_fees_recommended(&self) -> Result<Response<GetFeesRecommendedResponse>> {
+    let request = Request::get("/fees/recommended");
+
+    let response = self
+      .inner
+      .transport
+      .send::<GetFeesRecommendedResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get details about an address.
-  /// Check [`GetAddressDetailsResponse`](self::types::GetAddressDetailsResponse)
-  /// for available data.
-  /// 
-  #[tokio::main]
-  pub async fn get_address_details(
+  /// Same as [`Blockchain::get_fees_mempool_blocks`], with the metadata of the response.
+  ///
+  pub async fn get_fees_mempool_blocks(
     &self,
-    GetAddressParams { address }: GetAddressParams,
-  ) -> Result<GetAddressDetailsResponse> {
-    let url = format!("{}/address/{}", self.base_url, address);
+  ) -> Result<Response<Vec<GetFeesMempoolBlocksResponse>>> {
+    let request = Request::get("/fees/mempool-blocks");
 
-    let client = self.client.get(url).header("Accept", "application/json");
+    let response = self
+      .inner
+      .transport
+      .send::<GetFeesMempoolBlocksResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+    Ok(response)
+  }
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetAddressDetailsResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+  /// Same as [`Blockchain::get_fees_mempool_blocks_lenient`], with the metadata of the response.
+  ///
+  pub async fn get_fees_mempool_blocks_lenient(
+    &self,
+  ) -> Result<Response<Partial<GetFeesMempoolBlocksResponse>>> {
+    let request = Request::get("/fees/mempool-blocks");
+
+    let response = self
+      .inner
+      .transport
+      .send::<ListJsonData>(request)
+      .await?
+      .map(|json| Partial::decode(json.data));
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get transaction history for the specified address/scripthash,
-  /// sorted with newest first.
-  /// Returns up to 50 mempool transactions plus the first 25 confirmed transactions.
-  /// 
-  #[tokio::main]
-  pub async fn get_address_transactions(
+  /// Same as [`Blockchain::get_address_details`], with the metadata of the response.
+  ///
+  pub async fn get_address_details(
     &self,
     GetAddressParams { address }: GetAddressParams,
-  ) -> Result<Vec<GetAddressTransactionsResponse>> {
-    let url = format!("{}/address/{}/txs", self.base_url, address);
-
-    let client = self.client.get(url).header("Accept", "application/json");
+  ) -> Result<Response<GetAddressDetailsResponse>> {
+    let request = Request::get("/address/{address}").path(format!("/address/{}", address));
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
-
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetAddressTransactionsResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetAddressDetailsResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get the list of unspent transaction outputs associated with the address/scripthash. 
-  /// 
-  #[tokio::main]
-  pub async fn get_address_utxos(
+  /// Same as [`Blockchain::get_address_transactions`], with the metadata of the response.
+  ///
+  pub async fn get_address_transactions(
     &self,
     GetAddressParams { address }: GetAddressParams,
-  ) -> Result<Vec<GetAddressUTXOResponse>> {
-    let url = format!("{}/address/{}/txs/utxo", self.base_url, address);
-
-    let client = self.client.get(url).header("Accept", "application/json");
+  ) -> Result<Response<Vec<GetAddressTransactionsResponse>>> {
+    let request = Request::get("/address/{address}/txs").path(format!("/address/{}/txs", address));
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
-
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetAddressUTXOResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetAddressTransactionsResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get network-wide hashrate and difficulty figures.
-  /// 
-  #[tokio::main]
-  pub async fn get_hashrate(&self) -> Result<GetHashrateResponse> {
-    let url = format!("{}/hashrate", self.base_url);
-
-    let client = self.client.get(url).header("Accept", "application/json");
-
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+  /// Same as [`Blockchain::get_address_transactions_lenient`], with the metadata of the response.
+  ///
+  pub async fn get_address_transactions_lenient(
+    &self,
+    GetAddressParams { address }: GetAddressParams,
+  ) -> Result<Response<Partial<GetAddressTransactionsResponse>>> {
+    let request = Request::get("/address/{address}/txs").path(format!("/address/{}/txs", address));
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetHashrateResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<ListJsonData>(request)
+      .await?
+      .map(|json| Partial::decode(json.data));
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get simple information regarding the health
-  /// of the blockchain service.
-  /// 
-  /// More info at [service-blockchain](https://github.com/murray-rothbot/service-blockchain).
-  /// 
-  #[tokio::main]
-  pub async fn get_health(&self) -> Result<GetHealthResponse> {
-    let url = format!("{}/health", self.base_url);
+  /// Same as [`Blockchain::get_address_utxos`], with the metadata of the response.
+  ///
+  pub async fn get_address_utxos(
+    &self,
+    GetAddressParams { address }: GetAddressParams,
+  ) -> Result<Response<Vec<GetAddressUTXOResponse>>> {
+    let request =
+      Request::get("/address/{address}/txs/utxo").path(format!("/address/{}/txs/utxo", address));
+
+    let response = self
+      .inner
+      .transport
+      .send::<GetAddressUTXOResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
+
+    Ok(response)
+  }
 
-    let client = self.client.get(url).header("Accept", "application/json");
+  /// Same as [`Blockchain::get_address_utxos_lenient`], with the metadata of the response.
+  ///
+  pub async fn get_address_utxos_lenient(
+    &self,
+    GetAddressParams { address }: GetAddressParams,
+  ) -> Result<Response<Partial<GetAddressUTXOResponse>>> {
+    let request =
+      Request::get("/address/{address}/txs/utxo").path(format!("/address/{}/txs/utxo", address));
+
+    let response = self
+      .inner
+      .transport
+      .send::<ListJsonData>(request)
+      .await?
+      .map(|json| Partial::decode(json.data));
+
+    Ok(response)
+  }
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+  /// Same as [`Blockchain::get_hashrate`], with the metadata of the response.
+  ///
+  pub async fn get_hashrate(&self) -> Result<Response<GetHashrateResponse>> {
+    let request = Request::get("/hashrate");
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetHealthResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetHashrateResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get current mempool backlog statistics.
-  /// 
-  #[tokio::main]
-  pub async fn get_mempool(&self) -> Result<GetMempoolResponse> {
-    let url = format!("{}/mempool", self.base_url);
+  /// Same as [`Blockchain::get_health`], with the metadata of the response.
+  ///
+  pub async fn get_health(&self) -> Result<Response<GetHealthResponse>> {
+    let request = Request::get("/health");
 
-    let client = self.client.get(url).header("Accept", "application/json");
+    let response = self
+      .inner
+      .transport
+      .send::<GetHealthResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+    Ok(response)
+  }
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetMempoolResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+  /// Same as [`Blockchain::get_mempool`], with the metadata of the response.
+  ///
+  pub async fn get_mempool(&self) -> Result<Response<GetMempoolResponse>> {
+    let request = Request::get("/mempool");
 
-    Ok(data)
+    let response = self
+      .inner
+      .transport
+      .send::<GetMempoolResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
+
+    Ok(response)
   }
 
-  /// Get details about a transaction.
-  /// 
-  #[tokio::main]
+  /// Same as [`Blockchain::get_transaction`], with the metadata of the response.
+  ///
   pub async fn get_transaction(
     &self,
     GetTransactionParams { txid }: GetTransactionParams,
-  ) -> Result<GetTransactionResponse> {
-    let url = format!("{}/tx/{}", self.base_url, txid);
-
-    let client = self.client.get(url).header("Accept", "application/json");
+  ) -> Result<Response<GetTransactionResponse>> {
+    let request = Request::get("/tx/{txid}")
+      .path(format!("/tx/{}", txid))
+      .immutable_once_confirmed();
+
+    #[cfg(feature = "disk-cache")]
+    if let Some(transaction) = self.inner.stored_transaction(txid).await {
+      return self.inner.transport.stored(&request, transaction);
+    }
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetTransactionResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetTransactionResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+    #[cfg(feature = "disk-cache")]
+    self.inner.store_transaction(response.data()).await;
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Broadcast a raw transaction to the network.
-  /// The transaction should be provided as hex in the request body.
-  /// The txid will be returned on success.
-  /// 
-  #[tokio::main]
+  /// Same as [`Blockchain::post_transaction`], with the metadata of the response.
+  ///
   pub async fn post_transaction(
     &self,
     PostTransactionParams { tx_hex }: PostTransactionParams,
-  ) -> Result<PostTransactionResponse> {
-    let url = format!("{}/tx", self.base_url);
-
+  ) -> Result<Response<PostTransactionResponse>> {
     let json_data = format!(r#"{{"txHex":"{}"}}"#, tx_hex);
 
-    let client = self
-      .client
-      .post(url)
-      .header("Accept", "application/json")
-      .body(json_data);
+    let request = Request::post("/tx").body(json_data);
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(BlockchainError::BadRequest(e.to_string())),
-    };
-
-    let data = match server_response {
-      Ok(res) => match res.json::<PostTransactionResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(BlockchainError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(BlockchainError::APIError(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<PostTransactionResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    Ok(data)
+    Ok(response)
   }
 }
@@ -1,17 +1,22 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Stats {
   pub funded_txo_count: u64,
   pub funded_txo_sum: u64,
   pub spent_txo_count: u64,
   pub spent_txo_sum: u64,
   pub tx_count: u32,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct AddressDetails {
   pub address: String,
   pub chain_stats: Stats,
   pub mempool_stats: Stats,
-}
\ No newline at end of file
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
+}
@@ -1,20 +1,37 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Deserialize, Serialize, Debug)]
+use super::BlockHash;
+
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Block2Time {
   pub timestamp: u64,
   pub height: u32,
   pub in_future: bool,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Pool {
   pub id: u64,
   pub name: String,
   pub slug: String,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct OrphanBlock {
+  pub height: u32,
+  pub hash: BlockHash,
+  pub status: String,
+  pub prevhash: BlockHash,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct Extras {
   pub avg_fee: Option<f64>,
@@ -32,7 +49,7 @@ pub struct Extras {
   pub match_rate: Option<f64>,
   pub median_fee: Option<f64>,
   pub median_fee_amt: Option<f64>,
-  pub orphans: Option<Vec<Block>>,
+  pub orphans: Option<Vec<OrphanBlock>>,
   pub pool: Option<Pool>,
   pub reward: Option<u64>,
   pub segwit_total_size: Option<u64>,
@@ -47,11 +64,13 @@ pub struct Extras {
   pub utxo_set_change: Option<f64>,
   pub utxo_set_size: Option<f64>,
   pub virtual_size: Option<f64>,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Block {
-  pub id: String,
+  pub id: BlockHash,
   pub height: u32,
   pub version: u32,
   pub timestamp: u32,
@@ -59,9 +78,11 @@ pub struct Block {
   pub nonce: u32,
   pub difficulty: f64,
   pub merkle_root: String,
-  pub tx_count: u16,
+  pub tx_count: u32,
   pub size: u32,
   pub weight: u32,
-  pub previousblockhash: String,
+  pub previousblockhash: Option<BlockHash>, // None for the genesis block
   pub mediantime: u32,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct FeesRecommended {
   pub fastest_fee: u64,
@@ -8,29 +9,37 @@ pub struct FeesRecommended {
   pub hour_fee: u64,
   pub economy_fee: u64,
   pub minimum_fee: u64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct FeesMempoolBlocks {
   pub block_size: u32,
-  pub block_v_size: f32,
+  pub block_v_size: f64,
   pub n_tx: u32,
   pub total_fees: u64,
   pub median_fee: f64,
   pub fee_range: Vec<f64>,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct FeeHistogramEntry {
-pub fee_rate: f64,
-pub vsize: u64,
+  pub fee_rate: f64,
+  pub vsize: u64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct MempoolData {
-pub count: u64,
-pub vsize: u64,
-pub total_fee: f64,
-pub fee_histogram: Vec<FeeHistogramEntry>,
+  pub count: u64,
+  pub vsize: u64,
+  pub total_fee: f64,
+  pub fee_histogram: Vec<FeeHistogramEntry>,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
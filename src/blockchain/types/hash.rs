@@ -0,0 +1,110 @@
+use std::{fmt, str::FromStr};
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Error returned when parsing a [`Txid`] or a [`BlockHash`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseHashError {
+  #[error("expected 64 hex characters, got {0}")]
+  InvalidLength(usize),
+  #[error("invalid hex character {0:?}")]
+  InvalidCharacter(char),
+}
+
+/// Defines a 32-byte hash, written as 64 hex characters
+/// in the order they are displayed by the services.
+macro_rules! hash {
+  ($(#[$doc:meta])* $name:ident) => {
+    $(#[$doc])*
+    #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+    pub struct $name([u8; 32]);
+
+    impl $name {
+      pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+      }
+
+      pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+      }
+    }
+
+    impl FromStr for $name {
+      type Err = ParseHashError;
+
+      fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_hex(s).map(Self)
+      }
+    }
+
+    impl fmt::Display for $name {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+      }
+    }
+
+    impl fmt::Debug for $name {
+      fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({})", stringify!($name), self)
+      }
+    }
+
+    impl Serialize for $name {
+      fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+      }
+    }
+
+    impl<'de> Deserialize<'de> for $name {
+      fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let hex = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        hex.parse().map_err(de::Error::custom)
+      }
+    }
+  };
+}
+
+hash! {
+  /// Id of a transaction, e.g.
+  /// `15e10745f15593a899cef391191bdd3d7c12412cc4696b7bcb669d0feadc8521`.
+  ///
+  /// ```
+  /// use murray_rs::Txid;
+  ///
+  /// let txid: Txid = "15e10745f15593a899cef391191bdd3d7c12412cc4696b7bcb669d0feadc8521"
+  ///   .parse()
+  ///   .unwrap();
+  /// assert!("15e1".parse::<Txid>().is_err());
+  /// ```
+  ///
+  Txid
+}
+
+hash! {
+  /// Hash of a block, e.g.
+  /// `0000000000000000000070311b00c527b7605025f572e328dc399039328afb87`.
+  ///
+  BlockHash
+}
+
+fn parse_hex(s: &str) -> Result<[u8; 32], ParseHashError> {
+  // so that the length in bytes is the number of characters
+  if let Some(c) = s.chars().find(|c| !c.is_ascii()) {
+    return Err(ParseHashError::InvalidCharacter(c));
+  }
+  if s.len() != 64 {
+    return Err(ParseHashError::InvalidLength(s.len()));
+  }
+
+  let mut bytes = [0; 32];
+  let digits = s.chars().map(|c| {
+    c.to_digit(16)
+      .map(|digit| digit as u8)
+      .ok_or(ParseHashError::InvalidCharacter(c))
+  });
+  for (i, digit) in digits.enumerate() {
+    bytes[i / 2] = (bytes[i / 2] << 4) | digit?;
+  }
+
+  Ok(bytes)
+}
@@ -1,22 +1,27 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct DifficultyEntry {
   pub time: u64,
   pub height: u64,
   pub difficulty: f64,
   pub adjustment: f64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct HashratesEntry {
   pub timestamp: u64,
   pub avg_hashrate: f64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct HashrateData {
   pub progress_percent: f64,
@@ -35,4 +40,6 @@ pub struct HashrateData {
   pub difficulty: Vec<DifficultyEntry>,
   pub current_hashrate: f64,
   pub current_difficulty: f64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
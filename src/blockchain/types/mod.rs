@@ -1,29 +1,58 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
+pub mod address;
 pub mod block;
 pub mod fee;
-pub mod transaction;
-pub mod address;
+mod hash;
 pub mod mining;
+pub mod transaction;
 
-use self::{address::AddressDetails, block::{Block, Block2Time, Extras}, fee::{FeesMempoolBlocks, FeesRecommended, MempoolData}, mining::HashrateData, transaction::{Transaction, TransactionStatus}};
+pub use self::hash::{BlockHash, ParseHashError, Txid};
+
+use self::{
+  address::AddressDetails,
+  block::{Block, Block2Time, Extras},
+  fee::{FeesMempoolBlocks, FeesRecommended, MempoolData},
+  mining::HashrateData,
+  transaction::{Transaction, TransactionStatus},
+};
 
 /// [`Blockchain`] error
-#[derive(thiserror::Error, Debug)]
-pub enum BlockchainError {
-  #[error("Invalid URL params: `{0}`")]
-  InvalidURLParams(String),
-  #[error("Bad request: `{0}`")]
-  BadRequest(String),
-  #[error("API error: `{0}`")]
-  APIError(String),
-  #[error("JSON parse error: `{0}`")]
-  JSONParseError(String),
+#[deprecated(since = "0.2.0", note = "use `MurrayError` instead")]
+pub type BlockchainError = crate::MurrayError;
+
+/// The block to get, found by its height or hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockRef {
+  /// The last block.
+  #[default]
+  Tip,
+  Height(u32),
+  Hash(BlockHash),
+}
+
+impl BlockRef {
+  /// Query params finding the block, none for the tip.
+  pub(crate) fn query(&self) -> Vec<(&'static str, String)> {
+    match self {
+      BlockRef::Tip => vec![],
+      BlockRef::Height(height) => vec![("height", height.to_string())],
+      BlockRef::Hash(hash) => vec![("hash", hash.to_string())],
+    }
+  }
 }
 
-pub struct GetBlockParams {
-  pub hash: Option<String>,
-  pub height: Option<u32>,
+impl From<u32> for BlockRef {
+  fn from(height: u32) -> Self {
+    Self::Height(height)
+  }
+}
+
+impl From<BlockHash> for BlockRef {
+  fn from(hash: BlockHash) -> Self {
+    Self::Hash(hash)
+  }
 }
 
 pub struct GetAddressParams {
@@ -31,79 +60,83 @@ pub struct GetAddressParams {
 }
 
 pub struct GetTransactionParams {
-  pub txid: String,
+  pub txid: Txid,
 }
 
 pub struct PostTransactionParams {
   pub tx_hex: String,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetBlockResponse {
   #[serde(flatten)]
   pub block: Block,
   pub extras: Option<Extras>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetBlock2TimeResponse {
   #[serde(flatten)]
-  pub block2time: Block2Time
+  pub block2time: Block2Time,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetFeesRecommendedResponse {
   #[serde(flatten)]
-  pub fees_recommended: FeesRecommended
+  pub fees_recommended: FeesRecommended,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetFeesMempoolBlocksResponse {
   #[serde(flatten)]
-  pub fees_mempool_blocks: FeesMempoolBlocks
+  pub fees_mempool_blocks: FeesMempoolBlocks,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetAddressDetailsResponse {
   #[serde(flatten)]
-  pub address_details: AddressDetails
+  pub address_details: AddressDetails,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetAddressTransactionsResponse {
   #[serde(flatten)]
-  pub address_transactions: Transaction
+  pub address_transactions: Transaction,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetAddressUTXOResponse {
-  pub txid: String,
+  pub txid: Txid,
   pub vout: u32,
   pub status: TransactionStatus,
-  pub value: u64
+  pub value: u64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetHashrateResponse {
   #[serde(flatten)]
-  pub hashrate: HashrateData
+  pub hashrate: HashrateData,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetMempoolResponse {
   #[serde(flatten)]
-  pub mempool_response: MempoolData
+  pub mempool_response: MempoolData,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetTransactionResponse {
   #[serde(flatten)]
-  pub transaction: Transaction
+  pub transaction: Transaction,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct PostTransactionResponse {
-  pub txid: String
+  pub txid: Txid,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -141,6 +174,12 @@ pub(super) struct GetAddressUTXOResponseJsonData {
   pub data: Vec<GetAddressUTXOResponse>,
 }
 
+/// A list response, whose items are decoded one by one.
+#[derive(Deserialize, Serialize)]
+pub(super) struct ListJsonData {
+  pub data: Vec<Value>,
+}
+
 #[derive(Deserialize, Serialize)]
 pub(super) struct GetHashrateResponseJsonData {
   pub data: GetHashrateResponse,
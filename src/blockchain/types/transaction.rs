@@ -1,46 +1,59 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Deserialize, Serialize, Debug)]
+use super::{BlockHash, Txid};
+
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct PreviousOutput {
   pub scriptpubkey: String,
   pub scriptpubkey_asm: String,
   pub scriptpubkey_type: String,
-  pub scriptpubkey_address: String,
+  // None for outputs without an address, e.g. OP_RETURN ones
+  pub scriptpubkey_address: Option<String>,
   pub value: u64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct TransactionInput {
-  pub txid: String,
+  pub txid: Txid,
   pub vout: u32,
-  pub prevout: PreviousOutput,
+  pub prevout: Option<PreviousOutput>, // None for coinbase inputs
   pub scriptsig: String,
   pub scriptsig_asm: String,
   pub witness: Option<Vec<String>>,
   pub is_coinbase: bool,
   pub sequence: u32,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct TransactionStatus {
   pub confirmed: bool,
   pub block_height: Option<u32>, // These fields can be Optional because the tx may have not been confirmed yet
-  pub block_hash: Option<String>,
+  pub block_hash: Option<BlockHash>,
   pub block_time: Option<u64>,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct TransactionOutput {
   pub scriptpubkey: String,
   pub scriptpubkey_asm: String,
   pub scriptpubkey_type: String,
-  pub scriptpubkey_address: String,
+  // None for outputs without an address, e.g. OP_RETURN ones
+  pub scriptpubkey_address: Option<String>,
   pub value: u64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Transaction {
-  pub txid: String,
+  pub txid: Txid,
   pub version: u32,
   pub locktime: u32,
   pub vin: Vec<TransactionInput>,
@@ -48,6 +61,8 @@ pub struct Transaction {
   pub size: u32,
   pub weight: u32,
   pub sigops: u32,
-  pub fee: u32,
+  pub fee: u64,
   pub status: TransactionStatus,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
@@ -0,0 +1,399 @@
+//! Synchronous version of the [`Murray`](crate::Murray) API.
+//!
+//! Enabled with the `blocking` cargo feature. Every call is
+//! driven by one lazily-created Tokio runtime shared by the
+//! whole process, so keep-alive connections are reused between
+//! calls instead of being thrown away with a per-call runtime.
+//!
+//! These methods block the current thread and therefore **must not**
+//! be called from within an async context; use the async
+//! [`Murray`](crate::Murray) there instead.
+//!
+use std::{future::Future, sync::OnceLock};
+
+use serde::de::DeserializeOwned;
+use tokio::runtime::Runtime;
+
+use crate::{
+  node_details::NodeData, statistics::Statistics, top_nodes::TopData, BaseEndpointsParams,
+  BlockRef, Cache, CircuitState, ConvertCurrencyParams, ConvertCurrencyReturn,
+  GetAddressDetailsResponse, GetAddressParams, GetAddressTransactionsResponse,
+  GetAddressUTXOResponse, GetBlock2TimeResponse, GetBlockResponse, GetFeesMempoolBlocksResponse,
+  GetFeesRecommendedResponse, GetHashrateResponse, GetHealthResponse, GetMempoolResponse,
+  GetNodeDetailsParams, GetTickerParams, GetTickerReturn, GetTickersReturn, GetTransactionParams,
+  GetTransactionResponse, MetricsSnapshot, Partial, PostTransactionParams, PostTransactionResponse,
+  RequestOptions, Result, Service,
+};
+
+/// Returns the runtime shared by all blocking calls,
+/// creating it on first use.
+fn runtime() -> &'static Runtime {
+  static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+  RUNTIME.get_or_init(|| {
+    tokio::runtime::Builder::new_multi_thread()
+      // connection tasks are the only thing spawned on it
+      .worker_threads(1)
+      .thread_name("murray-rs-blocking")
+      .enable_all()
+      .build()
+      .expect("failed to build the murray-rs blocking runtime")
+  })
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+  runtime().block_on(future)
+}
+
+/// Blocking counterpart of [`Murray`](crate::Murray).
+///
+/// It exposes the same `blockchain`, `prices` and `lightning`
+/// services, but every method returns its result directly.
+///
+#[derive(Clone)]
+pub struct Murray {
+  pub blockchain: Blockchain,
+  pub prices: Prices,
+  pub lightning: Lightning,
+  cache: Option<Cache>,
+  metrics: crate::metrics::Metrics,
+}
+
+impl Murray {
+  pub fn new(params: BaseEndpointsParams) -> Self {
+    Self::from(crate::Murray::new(params))
+  }
+
+  /// See [`crate::Murray::cache`].
+  ///
+  pub fn cache(&self) -> Option<&Cache> {
+    self.cache.as_ref()
+  }
+
+  /// See [`crate::Murray::metrics`].
+  ///
+  pub fn metrics(&self) -> MetricsSnapshot {
+    self.metrics.snapshot()
+  }
+
+  /// See [`crate::Murray::call`].
+  ///
+  pub fn call<T: DeserializeOwned>(
+    &self,
+    service: Service,
+    path: &str,
+    query: &[(&str, &str)],
+  ) -> Result<T> {
+    let transport = match service {
+      Service::Blockchain => self.blockchain.inner.transport(),
+      Service::Lightning => self.lightning.inner.transport(),
+      Service::Prices => self.prices.inner.transport(),
+    };
+    block_on(crate::call(transport, path, query))
+  }
+
+  /// See [`crate::Murray::call_json`].
+  ///
+  pub fn call_json(
+    &self,
+    service: Service,
+    path: &str,
+    query: &[(&str, &str)],
+  ) -> Result<serde_json::Value> {
+    self.call(service, path, query)
+  }
+}
+
+impl Default for Murray {
+  /// Creates a default instance of the blocking Murray
+  /// with the same default endpoints as [`Murray::default`](crate::Murray::default).
+  ///
+  fn default() -> Self {
+    Self::new(BaseEndpointsParams::default())
+  }
+}
+
+impl From<crate::Murray> for Murray {
+  fn from(murray: crate::Murray) -> Self {
+    Self {
+      blockchain: Blockchain {
+        inner: murray.blockchain,
+      },
+      prices: Prices {
+        inner: murray.prices,
+      },
+      lightning: Lightning {
+        inner: murray.lightning,
+      },
+      cache: murray.cache,
+      metrics: murray.metrics,
+    }
+  }
+}
+
+/// Blocking client for the blockchain service.
+/// See [`crate::Blockchain`] for the async version.
+///
+#[derive(Clone)]
+pub struct Blockchain {
+  inner: crate::Blockchain,
+}
+
+impl Blockchain {
+  /// Change the base url on the fly for the [`Blockchain`] calls.
+  ///
+  pub fn set_base_url(&mut self, base_url: String) {
+    self.inner.set_base_url(base_url);
+  }
+
+  /// Same as [`crate::Blockchain::circuit_state`].
+  ///
+  pub fn circuit_state(&self) -> Option<CircuitState> {
+    self.inner.circuit_state()
+  }
+
+  /// Same as [`crate::Blockchain::with_options`].
+  ///
+  pub fn with_options(&self, options: RequestOptions) -> Self {
+    Self {
+      inner: self.inner.with_options(options),
+    }
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_block`].
+  ///
+  pub fn get_block(&self, block: BlockRef) -> Result<GetBlockResponse> {
+    block_on(self.inner.get_block(block))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_block2time`].
+  ///
+  pub fn get_block2time(&self, block: BlockRef) -> Result<GetBlock2TimeResponse> {
+    block_on(self.inner.get_block2time(block))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_fees_recommended`].
+  ///
+  pub fn get_fees_recommended(&self) -> Result<GetFeesRecommendedResponse> {
+    block_on(self.inner.get_fees_recommended())
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_fees_mempool_blocks`].
+  ///
+  pub fn get_fees_mempool_blocks(&self) -> Result<Vec<GetFeesMempoolBlocksResponse>> {
+    block_on(self.inner.get_fees_mempool_blocks())
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_fees_mempool_blocks_lenient`].
+  ///
+  pub fn get_fees_mempool_blocks_lenient(&self) -> Result<Partial<GetFeesMempoolBlocksResponse>> {
+    block_on(self.inner.get_fees_mempool_blocks_lenient())
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_address_details`].
+  ///
+  pub fn get_address_details(&self, params: GetAddressParams) -> Result<GetAddressDetailsResponse> {
+    block_on(self.inner.get_address_details(params))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_address_transactions`].
+  ///
+  pub fn get_address_transactions(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Vec<GetAddressTransactionsResponse>> {
+    block_on(self.inner.get_address_transactions(params))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_address_transactions_lenient`].
+  ///
+  pub fn get_address_transactions_lenient(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Partial<GetAddressTransactionsResponse>> {
+    block_on(self.inner.get_address_transactions_lenient(params))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_address_utxos`].
+  ///
+  pub fn get_address_utxos(&self, params: GetAddressParams) -> Result<Vec<GetAddressUTXOResponse>> {
+    block_on(self.inner.get_address_utxos(params))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_address_utxos_lenient`].
+  ///
+  pub fn get_address_utxos_lenient(
+    &self,
+    params: GetAddressParams,
+  ) -> Result<Partial<GetAddressUTXOResponse>> {
+    block_on(self.inner.get_address_utxos_lenient(params))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_hashrate`].
+  ///
+  pub fn get_hashrate(&self) -> Result<GetHashrateResponse> {
+    block_on(self.inner.get_hashrate())
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_health`].
+  ///
+  pub fn get_health(&self) -> Result<GetHealthResponse> {
+    block_on(self.inner.get_health())
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_mempool`].
+  ///
+  pub fn get_mempool(&self) -> Result<GetMempoolResponse> {
+    block_on(self.inner.get_mempool())
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_transaction`].
+  ///
+  pub fn get_transaction(&self, params: GetTransactionParams) -> Result<GetTransactionResponse> {
+    block_on(self.inner.get_transaction(params))
+  }
+
+  /// Blocking version of [`crate::Blockchain::post_transaction`].
+  ///
+  pub fn post_transaction(&self, params: PostTransactionParams) -> Result<PostTransactionResponse> {
+    block_on(self.inner.post_transaction(params))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_blocks`].
+  ///
+  pub fn get_blocks(
+    &self,
+    blocks: impl IntoIterator<Item = BlockRef>,
+    concurrency: usize,
+  ) -> Vec<Result<GetBlockResponse>> {
+    block_on(self.inner.get_blocks(blocks, concurrency))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_addresses_details`].
+  ///
+  pub fn get_addresses_details(
+    &self,
+    params: impl IntoIterator<Item = GetAddressParams>,
+    concurrency: usize,
+  ) -> Vec<Result<GetAddressDetailsResponse>> {
+    block_on(self.inner.get_addresses_details(params, concurrency))
+  }
+
+  /// Blocking version of [`crate::Blockchain::get_transactions`].
+  ///
+  pub fn get_transactions(
+    &self,
+    params: impl IntoIterator<Item = GetTransactionParams>,
+    concurrency: usize,
+  ) -> Vec<Result<GetTransactionResponse>> {
+    block_on(self.inner.get_transactions(params, concurrency))
+  }
+}
+
+/// Blocking client for the lightning service.
+/// See [`crate::Lightning`] for the async version.
+///
+#[derive(Clone)]
+pub struct Lightning {
+  inner: crate::Lightning,
+}
+
+impl Lightning {
+  /// Change the base url on the fly for the [`Lightning`] calls.
+  ///
+  pub fn set_base_url(&mut self, base_url: String) {
+    self.inner.set_base_url(base_url);
+  }
+
+  /// Same as [`crate::Lightning::circuit_state`].
+  ///
+  pub fn circuit_state(&self) -> Option<CircuitState> {
+    self.inner.circuit_state()
+  }
+
+  /// Same as [`crate::Lightning::with_options`].
+  ///
+  pub fn with_options(&self, options: RequestOptions) -> Self {
+    Self {
+      inner: self.inner.with_options(options),
+    }
+  }
+
+  /// Blocking version of [`crate::Lightning::get_node_details`].
+  ///
+  pub fn get_node_details(&self, params: GetNodeDetailsParams) -> Result<NodeData> {
+    block_on(self.inner.get_node_details(params))
+  }
+
+  /// Blocking version of [`crate::Lightning::get_statistics`].
+  ///
+  pub fn get_statistics(&self) -> Result<Statistics> {
+    block_on(self.inner.get_statistics())
+  }
+
+  /// Blocking version of [`crate::Lightning::get_top_nodes`].
+  ///
+  pub fn get_top_nodes(&self) -> Result<TopData> {
+    block_on(self.inner.get_top_nodes())
+  }
+
+  /// Blocking version of [`crate::Lightning::get_health`].
+  ///
+  pub fn get_health(&self) -> Result<GetHealthResponse> {
+    block_on(self.inner.get_health())
+  }
+}
+
+/// Blocking client for the prices service.
+/// See [`crate::Prices`] for the async version.
+///
+#[derive(Clone)]
+pub struct Prices {
+  inner: crate::Prices,
+}
+
+impl Prices {
+  /// Change the base url on the fly for the [`Prices`] calls.
+  ///
+  pub fn set_base_url(&mut self, base_url: String) {
+    self.inner.set_base_url(base_url);
+  }
+
+  /// Same as [`crate::Prices::circuit_state`].
+  ///
+  pub fn circuit_state(&self) -> Option<CircuitState> {
+    self.inner.circuit_state()
+  }
+
+  /// Same as [`crate::Prices::with_options`].
+  ///
+  pub fn with_options(&self, options: RequestOptions) -> Self {
+    Self {
+      inner: self.inner.with_options(options),
+    }
+  }
+
+  /// Blocking version of [`crate::Prices::convert_currency`].
+  ///
+  pub fn convert_currency(&self, params: ConvertCurrencyParams) -> Result<ConvertCurrencyReturn> {
+    block_on(self.inner.convert_currency(params))
+  }
+
+  /// Blocking version of [`crate::Prices::get_ticker`].
+  ///
+  pub fn get_ticker(&self, params: GetTickerParams) -> Result<GetTickerReturn> {
+    block_on(self.inner.get_ticker(params))
+  }
+
+  /// Blocking version of [`crate::Prices::get_tickers`].
+  ///
+  pub fn get_tickers(&self, params: GetTickerParams) -> Result<GetTickersReturn> {
+    block_on(self.inner.get_tickers(params))
+  }
+
+  /// Blocking version of [`crate::Prices::get_health`].
+  ///
+  pub fn get_health(&self) -> Result<GetHealthResponse> {
+    block_on(self.inner.get_health())
+  }
+}
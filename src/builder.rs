@@ -0,0 +1,453 @@
+use std::{collections::HashMap, time::Duration};
+
+use reqwest::{header::HeaderMap, Certificate, Client, Proxy};
+
+use crate::{
+  auth::Auth,
+  blockchain::Blockchain,
+  cache::Cache,
+  config::{Config, ConfigError},
+  lightning::Lightning,
+  metrics::Metrics,
+  middleware::{self, BoxError, BoxedLayer, HttpService},
+  prices::Prices,
+  transport::Transport,
+  BaseEndpointsParams, CacheConfig, CircuitBreakerConfig, FailoverPolicy, Murray, RateLimit,
+  RetryPolicy, Service, BASE_BLOCKCHAIN_URL, BASE_LIGHTNING_URL, BASE_PRICES_URL,
+};
+
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// [`MurrayBuilder`] error
+#[derive(thiserror::Error, Debug)]
+pub enum BuilderError {
+  #[error("HTTP client error: `{0}`")]
+  Client(#[from] reqwest::Error),
+}
+
+/// Builds a [`Murray`] with a custom HTTP configuration.
+///
+/// Unless told otherwise, the HTTP client gives up connecting
+/// after 10 seconds and on the whole request after 30 seconds,
+/// identifies itself as `murray-rs/<version>`, and retries
+/// failed calls following the default [`RetryPolicy`].
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use murray_rs::Murray;
+///
+/// let murray = Murray::builder()
+///   .blockchain_endpoint("https://blockchain.example.com")
+///   .timeout(Duration::from_secs(5))
+///   .user_agent("my-app/1.0")
+///   .build()
+///   .unwrap();
+/// ```
+///
+pub struct MurrayBuilder {
+  endpoints: BaseEndpointsParams,
+  client: Option<Client>,
+  connect_timeout: Option<Duration>,
+  timeout: Option<Duration>,
+  user_agent: String,
+  default_headers: HeaderMap,
+  proxies: Vec<Proxy>,
+  root_certificates: Vec<Certificate>,
+  tls_built_in_root_certs: bool,
+  retry_policy: RetryPolicy,
+  failover_policy: FailoverPolicy,
+  rate_limits: HashMap<Service, RateLimit>,
+  circuit_breakers: HashMap<Service, CircuitBreakerConfig>,
+  auth: HashMap<Service, Auth>,
+  cache: Option<CacheConfig>,
+  single_flight: bool,
+  layers: Vec<BoxedLayer>,
+  #[cfg(feature = "test-utils")]
+  cassette: Option<crate::Cassette>,
+  #[cfg(feature = "tracing")]
+  redact_addresses: bool,
+  #[cfg(feature = "disk-cache")]
+  disk_cache: Option<crate::DiskCacheConfig>,
+}
+
+impl Default for MurrayBuilder {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl MurrayBuilder {
+  pub fn new() -> Self {
+    Self {
+      endpoints: BaseEndpointsParams::default(),
+      client: None,
+      connect_timeout: Some(DEFAULT_CONNECT_TIMEOUT),
+      timeout: Some(DEFAULT_TIMEOUT),
+      user_agent: DEFAULT_USER_AGENT.to_string(),
+      default_headers: HeaderMap::new(),
+      proxies: vec![],
+      root_certificates: vec![],
+      tls_built_in_root_certs: true,
+      retry_policy: RetryPolicy::default(),
+      failover_policy: FailoverPolicy::default(),
+      rate_limits: HashMap::new(),
+      circuit_breakers: HashMap::new(),
+      auth: HashMap::new(),
+      cache: None,
+      single_flight: true,
+      layers: vec![],
+      #[cfg(feature = "test-utils")]
+      cassette: None,
+      #[cfg(feature = "tracing")]
+      redact_addresses: false,
+      #[cfg(feature = "disk-cache")]
+      disk_cache: None,
+    }
+  }
+
+  /// A builder configured from the `MURRAY_*` environment
+  /// variables, see [`Murray::from_env`].
+  ///
+  pub fn from_env() -> Result<Self, ConfigError> {
+    Config::from_env()?.apply(Self::new(), |service, setting| {
+      format!(
+        "MURRAY_{}_{}",
+        service.to_string().to_uppercase(),
+        setting.to_uppercase()
+      )
+    })
+  }
+
+  /// A builder configured from a TOML or YAML file,
+  /// see [`Murray::from_config_file`].
+  ///
+  #[cfg(feature = "config-file")]
+  pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, ConfigError> {
+    Config::from_file(path.as_ref())?.apply(Self::new(), |service, setting| {
+      format!("{}.auth.{}", service, setting)
+    })
+  }
+
+  /// Sets all endpoints at once, like [`Murray::new`] does.
+  ///
+  pub fn endpoints(mut self, endpoints: BaseEndpointsParams) -> Self {
+    self.endpoints = endpoints;
+    self
+  }
+
+  pub fn blockchain_endpoint(mut self, url: impl Into<String>) -> Self {
+    self.endpoints.blockchain_endpoint = Some(url.into());
+    self
+  }
+
+  pub fn prices_endpoint(mut self, url: impl Into<String>) -> Self {
+    self.endpoints.prices_endpoint = Some(url.into());
+    self
+  }
+
+  pub fn lightning_endpoint(mut self, url: impl Into<String>) -> Self {
+    self.endpoints.lightning_endpoint = Some(url.into());
+    self
+  }
+
+  /// Base URLs of `service` to fall back to, in order,
+  /// when its main endpoint is down.
+  ///
+  pub fn fallback_endpoints(
+    mut self,
+    service: Service,
+    urls: impl IntoIterator<Item = impl Into<String>>,
+  ) -> Self {
+    let urls = urls.into_iter().map(Into::into).collect();
+    match service {
+      Service::Blockchain => self.endpoints.blockchain_fallback_endpoints = urls,
+      Service::Prices => self.endpoints.prices_fallback_endpoints = urls,
+      Service::Lightning => self.endpoints.lightning_fallback_endpoints = urls,
+    }
+    self
+  }
+
+  /// How calls are spread over, and fail over between, the
+  /// endpoints of a service. See [`FailoverPolicy`] for the defaults.
+  ///
+  pub fn failover_policy(mut self, failover_policy: FailoverPolicy) -> Self {
+    self.failover_policy = failover_policy;
+    self
+  }
+
+  /// Uses an already configured [`Client`] for all services.
+  ///
+  /// When set, it is used as is: the timeouts, user agent, default
+  /// headers, proxies and certificates of this builder are ignored.
+  ///
+  pub fn client(mut self, client: Client) -> Self {
+    self.client = Some(client);
+    self
+  }
+
+  /// Timeout for establishing a connection. `None` waits forever.
+  ///
+  pub fn connect_timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+    self.connect_timeout = timeout.into();
+    self
+  }
+
+  /// Timeout for a whole request, from connecting until the
+  /// response body has been read. `None` waits forever.
+  ///
+  pub fn timeout(mut self, timeout: impl Into<Option<Duration>>) -> Self {
+    self.timeout = timeout.into();
+    self
+  }
+
+  pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+    self.user_agent = user_agent.into();
+    self
+  }
+
+  /// Headers sent with every request.
+  /// Can be called several times, headers are merged.
+  ///
+  pub fn default_headers(mut self, headers: HeaderMap) -> Self {
+    self.default_headers.extend(headers);
+    self
+  }
+
+  /// Routes requests through an HTTP proxy.
+  /// Can be called several times to add more proxies.
+  ///
+  pub fn proxy(mut self, proxy: Proxy) -> Self {
+    self.proxies.push(proxy);
+    self
+  }
+
+  /// Trusts an additional root certificate, e.g. the one
+  /// of a self-hosted service using a private CA.
+  ///
+  pub fn add_root_certificate(mut self, certificate: Certificate) -> Self {
+    self.root_certificates.push(certificate);
+    self
+  }
+
+  /// Whether to trust the system/built-in root certificates (default `true`).
+  ///
+  pub fn tls_built_in_root_certs(mut self, enabled: bool) -> Self {
+    self.tls_built_in_root_certs = enabled;
+    self
+  }
+
+  /// How failed calls are retried, for all services.
+  /// See [`RetryPolicy`] for the defaults.
+  ///
+  pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+    self.retry_policy = retry_policy;
+    self
+  }
+
+  /// Limits how many calls are sent to `service`.
+  /// The limit is shared by all clones of the built [`Murray`].
+  ///
+  pub fn rate_limit(mut self, service: Service, rate_limit: RateLimit) -> Self {
+    self.rate_limits.insert(service, rate_limit);
+    self
+  }
+
+  /// Makes calls to `service` fail right away while it is down.
+  /// Its state is shared by all clones of the built [`Murray`].
+  ///
+  pub fn circuit_breaker(mut self, service: Service, config: CircuitBreakerConfig) -> Self {
+    self.circuit_breakers.insert(service, config);
+    self
+  }
+
+  /// Credentials sent with every request to `service`, see [`Auth`].
+  ///
+  pub fn auth(mut self, service: Service, auth: Auth) -> Self {
+    self.auth.insert(service, auth);
+    self
+  }
+
+  /// Leaves addresses out of the paths recorded in the `tracing`
+  /// spans of the calls, e.g. `/address/{address}/txs` instead
+  /// of the actual address (default `false`).
+  ///
+  #[cfg(feature = "tracing")]
+  pub fn redact_addresses(mut self, enabled: bool) -> Self {
+    self.redact_addresses = enabled;
+    self
+  }
+
+  /// Caches responses in memory, see [`CacheConfig`] for
+  /// the defaults. Off unless this is called.
+  ///
+  pub fn cache(mut self, config: CacheConfig) -> Self {
+    self.cache = Some(config);
+    self
+  }
+
+  /// Whether identical requests (same service, path and query)
+  /// sent while one of them is in flight share its response
+  /// instead of each making a call (default `true`).
+  ///
+  pub fn single_flight(mut self, enabled: bool) -> Self {
+    self.single_flight = enabled;
+    self
+  }
+
+  /// Wraps the HTTP requests of every service in a [`tower`]
+  /// middleware, e.g. to add headers, log or inject failures.
+  /// See [`HttpService`] for which requests go through it.
+  ///
+  /// The first layer added is the outermost one, as with
+  /// [`tower::ServiceBuilder`](https://docs.rs/tower/latest/tower/struct.ServiceBuilder.html).
+  ///
+  /// ```no_run
+  /// use murray_rs::{reqwest::header::HeaderValue, tower::util::MapRequestLayer, Murray};
+  ///
+  /// let murray = Murray::builder()
+  ///   .layer(MapRequestLayer::new(|mut request: murray_rs::reqwest::Request| {
+  ///     request
+  ///       .headers_mut()
+  ///       .insert("x-request-source", HeaderValue::from_static("my-app"));
+  ///     request
+  ///   }))
+  ///   .build()
+  ///   .unwrap();
+  /// ```
+  ///
+  pub fn layer<L>(mut self, layer: L) -> Self
+  where
+    L: tower::Layer<HttpService> + Send + Sync + 'static,
+    L::Service: tower::Service<reqwest::Request, Response = reqwest::Response>
+      + Clone
+      + Send
+      + Sync
+      + 'static,
+    <L::Service as tower::Service<reqwest::Request>>::Error: Into<BoxError>,
+    <L::Service as tower::Service<reqwest::Request>>::Future: Send + 'static,
+  {
+    self.layers.push(middleware::boxed_layer(layer));
+    self
+  }
+
+  /// Records the HTTP interactions of every service into a
+  /// [`Cassette`](crate::Cassette), or replays them from it.
+  /// It sits below the layers added with [`layer`](Self::layer).
+  ///
+  #[cfg(feature = "test-utils")]
+  pub fn cassette(mut self, cassette: crate::Cassette) -> Self {
+    self.cassette = Some(cassette);
+    self
+  }
+
+  /// Stores blocks and confirmed transactions on disk, so that
+  /// they survive restarts. See [`DiskCacheConfig`](crate::DiskCacheConfig).
+  ///
+  #[cfg(feature = "disk-cache")]
+  pub fn disk_cache(mut self, config: crate::DiskCacheConfig) -> Self {
+    self.disk_cache = Some(config);
+    self
+  }
+
+  pub fn build(self) -> Result<Murray, BuilderError> {
+    let client = match self.client {
+      Some(client) => client,
+      None => {
+        let mut builder = Client::builder()
+          .user_agent(self.user_agent)
+          .default_headers(self.default_headers)
+          .tls_built_in_root_certs(self.tls_built_in_root_certs);
+
+        if let Some(timeout) = self.connect_timeout {
+          builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+          builder = builder.timeout(timeout);
+        }
+        for proxy in self.proxies {
+          builder = builder.proxy(proxy);
+        }
+        for certificate in self.root_certificates {
+          builder = builder.add_root_certificate(certificate);
+        }
+
+        builder.build()?
+      }
+    };
+
+    let BaseEndpointsParams {
+      blockchain_endpoint,
+      prices_endpoint,
+      lightning_endpoint,
+      blockchain_fallback_endpoints,
+      prices_fallback_endpoints,
+      lightning_fallback_endpoints,
+    } = self.endpoints;
+
+    let blockchain_url = blockchain_endpoint.unwrap_or_else(|| BASE_BLOCKCHAIN_URL.to_string());
+    let prices_url = prices_endpoint.unwrap_or_else(|| BASE_PRICES_URL.to_string());
+    let lightning_url = lightning_endpoint.unwrap_or_else(|| BASE_LIGHTNING_URL.to_string());
+
+    let cache = self.cache.map(Cache::new);
+    let metrics = Metrics::default();
+
+    let transport = |service, url, fallbacks| {
+      let http = middleware::client(client.clone());
+      #[cfg(feature = "test-utils")]
+      let http = match &self.cassette {
+        Some(cassette) => cassette.wrap(service, http),
+        None => http,
+      };
+      let transport = Transport::new(service, url, middleware::wrap(http, &self.layers))
+        .with_fallbacks(fallbacks, self.failover_policy.clone())
+        .with_retry_policy(self.retry_policy.clone())
+        .with_rate_limit(self.rate_limits.get(&service).cloned())
+        .with_circuit_breaker(self.circuit_breakers.get(&service).cloned())
+        .with_auth(self.auth.get(&service).cloned())
+        .with_cache(cache.clone())
+        .with_metrics(metrics.clone())
+        .with_single_flight(self.single_flight);
+      #[cfg(feature = "tracing")]
+      let transport = transport.with_redacted_addresses(self.redact_addresses);
+      transport
+    };
+
+    let blockchain = Blockchain::from_transport(transport(
+      Service::Blockchain,
+      blockchain_url,
+      blockchain_fallback_endpoints,
+    ));
+    #[cfg(feature = "disk-cache")]
+    let blockchain = blockchain.with_disk_cache(
+      self
+        .disk_cache
+        .map(crate::blockchain::disk_cache::DiskCache::new),
+    );
+
+    Ok(Murray {
+      blockchain,
+      prices: Prices::from_transport(transport(
+        Service::Prices,
+        prices_url,
+        prices_fallback_endpoints,
+      )),
+      lightning: Lightning::from_transport(transport(
+        Service::Lightning,
+        lightning_url,
+        lightning_fallback_endpoints,
+      )),
+      cache,
+      metrics,
+    })
+  }
+
+  /// Same as [`build`](Self::build), but for the
+  /// [`blocking`](crate::blocking) API.
+  ///
+  #[cfg(feature = "blocking")]
+  pub fn build_blocking(self) -> Result<crate::blocking::Murray, BuilderError> {
+    self.build().map(crate::blocking::Murray::from)
+  }
+}
@@ -0,0 +1,399 @@
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use serde_json::Value;
+
+use crate::Service;
+
+/// How long a cached response stays fresh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheTtl {
+  /// The response is not cached.
+  Disabled,
+  /// The response is served from the cache for this long.
+  For(Duration),
+  /// The response is served from the cache until evicted or invalidated.
+  Forever,
+}
+
+/// Whether a response can change once it has been served.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Cacheability {
+  Volatile,
+  /// e.g. a block requested by its height or hash.
+  Immutable,
+  /// e.g. a transaction, which can't change once it is confirmed.
+  ImmutableOnceConfirmed,
+}
+
+/// Configuration of the in-memory response cache.
+///
+/// Immutable data (blocks requested by height or hash, confirmed
+/// transactions) is cached forever. Other endpoints are cached for
+/// a few seconds, depending on how often they change, and health
+/// checks are never cached:
+///
+/// | Service    | Endpoint                                                | TTL |
+/// |------------|---------------------------------------------------------|-----|
+/// | blockchain | `/block`, `/block2time` (tip), `/fees/*`, `/mempool`    | 10s |
+/// | blockchain | `/tx/{txid}` (unconfirmed), `/address/*`                | 10s |
+/// | blockchain | `/hashrate`                                             | 60s |
+/// | lightning  | `/node/{public_key}`, `/statistics`, `/top`             | 60s |
+/// | prices     | `/convert`, `/ticker`, `/tickers`                       | 5s  |
+///
+/// ```
+/// use std::time::Duration;
+/// use murray_rs::{CacheConfig, CacheTtl, Murray, Service};
+///
+/// let murray = Murray::builder()
+///   .cache(
+///     CacheConfig::default()
+///       .max_entries(10_000)
+///       .ttl(Service::Prices, "/ticker", CacheTtl::For(Duration::from_secs(1))),
+///   )
+///   .build()
+///   .unwrap();
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+  max_entries: usize,
+  max_bytes: usize,
+  immutable_ttl: CacheTtl,
+  default_ttl: CacheTtl,
+  ttls: HashMap<(Service, &'static str), CacheTtl>,
+}
+
+impl Default for CacheConfig {
+  fn default() -> Self {
+    let seconds = |s| CacheTtl::For(Duration::from_secs(s));
+
+    let ttls = HashMap::from([
+      ((Service::Blockchain, "/block"), seconds(10)),
+      ((Service::Blockchain, "/block2time"), seconds(10)),
+      ((Service::Blockchain, "/fees/recommended"), seconds(10)),
+      ((Service::Blockchain, "/fees/mempool-blocks"), seconds(10)),
+      ((Service::Blockchain, "/mempool"), seconds(10)),
+      ((Service::Blockchain, "/tx/{txid}"), seconds(10)),
+      ((Service::Blockchain, "/address/{address}"), seconds(10)),
+      ((Service::Blockchain, "/address/{address}/txs"), seconds(10)),
+      (
+        (Service::Blockchain, "/address/{address}/txs/utxo"),
+        seconds(10),
+      ),
+      ((Service::Blockchain, "/hashrate"), seconds(60)),
+      ((Service::Lightning, "/node/{public_key}"), seconds(60)),
+      ((Service::Lightning, "/statistics"), seconds(60)),
+      ((Service::Lightning, "/top"), seconds(60)),
+      ((Service::Prices, "/convert"), seconds(5)),
+      ((Service::Prices, "/ticker"), seconds(5)),
+      ((Service::Prices, "/tickers"), seconds(5)),
+    ]);
+
+    Self {
+      max_entries: 1_000,
+      max_bytes: 64 * 1024 * 1024,
+      immutable_ttl: CacheTtl::Forever,
+      default_ttl: CacheTtl::Disabled,
+      ttls,
+    }
+  }
+}
+
+impl CacheConfig {
+  /// Most responses kept at once (default 1000).
+  /// The least recently used ones are evicted first.
+  ///
+  pub fn max_entries(mut self, max_entries: usize) -> Self {
+    self.max_entries = max_entries;
+    self
+  }
+
+  /// Most bytes of response bodies kept at once (default 64 MiB).
+  ///
+  pub fn max_bytes(mut self, max_bytes: usize) -> Self {
+    self.max_bytes = max_bytes;
+    self
+  }
+
+  /// TTL of `endpoint` of `service`, given as its route
+  /// template, e.g. `/tx/{txid}`. For endpoints serving
+  /// immutable data, it only applies while the data may
+  /// still change (the tip block, unconfirmed transactions).
+  ///
+  pub fn ttl(mut self, service: Service, endpoint: &'static str, ttl: CacheTtl) -> Self {
+    self.ttls.insert((service, endpoint), ttl);
+    self
+  }
+
+  /// TTL of immutable data: blocks requested by
+  /// height or hash, and confirmed transactions.
+  ///
+  pub fn immutable_ttl(mut self, ttl: CacheTtl) -> Self {
+    self.immutable_ttl = ttl;
+    self
+  }
+
+  /// TTL of endpoints without a TTL of their own (default disabled).
+  ///
+  pub fn default_ttl(mut self, ttl: CacheTtl) -> Self {
+    self.default_ttl = ttl;
+    self
+  }
+
+  fn endpoint_ttl(&self, service: Service, endpoint: &'static str) -> CacheTtl {
+    self
+      .ttls
+      .get(&(service, endpoint))
+      .copied()
+      .unwrap_or(self.default_ttl)
+  }
+}
+
+/// Counters of the response cache, see [`Cache::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+  pub hits: u64,
+  pub misses: u64,
+  /// Entries dropped because they expired or didn't fit in the cache.
+  pub evictions: u64,
+  pub entries: usize,
+  pub bytes: usize,
+}
+
+impl CacheStats {
+  /// Share of lookups served from the cache, between 0 and 1.
+  pub fn hit_rate(&self) -> f64 {
+    let lookups = self.hits + self.misses;
+    if lookups == 0 {
+      0.0
+    } else {
+      self.hits as f64 / lookups as f64
+    }
+  }
+}
+
+/// In-memory cache of responses, shared by all services
+/// and clones of a [`Murray`](crate::Murray).
+///
+/// Get it from [`Murray::cache`](crate::Murray::cache) to
+/// invalidate entries or read its [`CacheStats`].
+///
+#[derive(Clone)]
+pub struct Cache {
+  config: Arc<CacheConfig>,
+  state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+  entries: HashMap<String, Entry>,
+  /// Incremented on every access, to find the least recently used entry.
+  clock: u64,
+  stats: CacheStats,
+}
+
+struct Entry {
+  service: Service,
+  endpoint: &'static str,
+  path: String,
+  body: Bytes,
+  expires_at: Option<Instant>,
+  used_at: u64,
+}
+
+impl Entry {
+  fn is_fresh(&self, now: Instant) -> bool {
+    self.expires_at.map(|at| now < at).unwrap_or(true)
+  }
+}
+
+impl Cache {
+  pub(crate) fn new(config: CacheConfig) -> Self {
+    Self {
+      config: Arc::new(config),
+      state: Arc::new(Mutex::new(State::default())),
+    }
+  }
+
+  fn state(&self) -> std::sync::MutexGuard<'_, State> {
+    self.state.lock().unwrap_or_else(|e| e.into_inner())
+  }
+
+  /// Whether a response of `endpoint` may end up in the cache,
+  /// i.e. whether looking it up is worth counting.
+  pub(crate) fn may_cache(
+    &self,
+    service: Service,
+    endpoint: &'static str,
+    cacheability: Cacheability,
+  ) -> bool {
+    let immutable =
+      cacheability != Cacheability::Volatile && self.config.immutable_ttl != CacheTtl::Disabled;
+    immutable || self.config.endpoint_ttl(service, endpoint) != CacheTtl::Disabled
+  }
+
+  pub(crate) fn get(&self, key: &str) -> Option<Bytes> {
+    let now = Instant::now();
+    let mut state = self.state();
+    state.clock += 1;
+    let clock = state.clock;
+
+    let fresh = match state.entries.get_mut(key) {
+      Some(entry) if entry.is_fresh(now) => {
+        entry.used_at = clock;
+        Some(entry.body.clone())
+      }
+      Some(_) => {
+        if let Some(entry) = state.entries.remove(key) {
+          state.stats.bytes -= entry.body.len();
+          state.stats.evictions += 1;
+        }
+        None
+      }
+      None => None,
+    };
+
+    match fresh {
+      Some(_) => state.stats.hits += 1,
+      None => state.stats.misses += 1,
+    }
+
+    fresh
+  }
+
+  pub(crate) fn insert(
+    &self,
+    key: String,
+    service: Service,
+    endpoint: &'static str,
+    path: String,
+    cacheability: Cacheability,
+    body: Bytes,
+  ) {
+    let immutable = match cacheability {
+      Cacheability::Volatile => false,
+      Cacheability::Immutable => true,
+      Cacheability::ImmutableOnceConfirmed => is_confirmed(&body),
+    };
+    let ttl = if immutable {
+      self.config.immutable_ttl
+    } else {
+      self.config.endpoint_ttl(service, endpoint)
+    };
+
+    let now = Instant::now();
+    let expires_at = match ttl {
+      CacheTtl::Disabled => return,
+      CacheTtl::For(ttl) => Some(now + ttl),
+      CacheTtl::Forever => None,
+    };
+    if body.len() > self.config.max_bytes || self.config.max_entries == 0 {
+      return;
+    }
+
+    let mut state = self.state();
+    state.clock += 1;
+    let entry = Entry {
+      service,
+      endpoint,
+      path,
+      used_at: state.clock,
+      body,
+      expires_at,
+    };
+
+    state.stats.bytes += entry.body.len();
+    if let Some(previous) = state.entries.insert(key, entry) {
+      state.stats.bytes -= previous.body.len();
+    }
+    self.evict(&mut state, now);
+  }
+
+  /// Drops expired entries, then the least recently used
+  /// ones, until the cache fits within its limits.
+  fn evict(&self, state: &mut State, now: Instant) {
+    if state.entries.len() <= self.config.max_entries && state.stats.bytes <= self.config.max_bytes
+    {
+      return;
+    }
+
+    let before = state.entries.len();
+    state.entries.retain(|_, entry| entry.is_fresh(now));
+
+    while state.entries.len() > self.config.max_entries || state.stats.bytes > self.config.max_bytes
+    {
+      let oldest = state
+        .entries
+        .iter()
+        .min_by_key(|(_, entry)| entry.used_at)
+        .map(|(key, _)| key.clone());
+      match oldest {
+        Some(key) => {
+          state.entries.remove(&key);
+        }
+        None => break,
+      }
+    }
+
+    state.stats.evictions += (before - state.entries.len()) as u64;
+    state.stats.bytes = state.entries.values().map(|entry| entry.body.len()).sum();
+  }
+
+  fn invalidate_where(&self, predicate: impl Fn(&Entry) -> bool) {
+    let mut state = self.state();
+    state.entries.retain(|_, entry| !predicate(entry));
+    state.stats.bytes = state.entries.values().map(|entry| entry.body.len()).sum();
+  }
+
+  /// Drops every cached response.
+  ///
+  pub fn invalidate_all(&self) {
+    self.invalidate_where(|_| true);
+  }
+
+  /// Drops every cached response of `service`.
+  ///
+  pub fn invalidate_service(&self, service: Service) {
+    self.invalidate_where(|entry| entry.service == service);
+  }
+
+  /// Drops every cached response of `endpoint` of `service`,
+  /// given as its route template, e.g. `/tx/{txid}`.
+  ///
+  pub fn invalidate_endpoint(&self, service: Service, endpoint: &str) {
+    self.invalidate_where(|entry| entry.service == service && entry.endpoint == endpoint);
+  }
+
+  /// Drops the cached responses of `service` for `path`,
+  /// e.g. `/tx/15e1...` or `/block?height=500000`.
+  ///
+  pub fn invalidate_path(&self, service: Service, path: &str) {
+    self.invalidate_where(|entry| entry.service == service && entry.path == path);
+  }
+
+  pub fn stats(&self) -> CacheStats {
+    let state = self.state();
+    CacheStats {
+      entries: state.entries.len(),
+      ..state.stats
+    }
+  }
+}
+
+/// Whether the `{ "data": { "status": { "confirmed": true } } }`
+/// of a transaction response is set.
+fn is_confirmed(body: &[u8]) -> bool {
+  serde_json::from_slice::<Value>(body)
+    .ok()
+    .and_then(|json| {
+      json
+        .pointer("/data/status/confirmed")
+        .and_then(Value::as_bool)
+    })
+    .unwrap_or(false)
+}
@@ -0,0 +1,315 @@
+//! Recording of the HTTP interactions of the services into a
+//! cassette file, to replay them later without any network,
+//! e.g. in CI. See [`Cassette`].
+
+use std::{
+  collections::BTreeMap,
+  future::Future,
+  io,
+  path::{Path, PathBuf},
+  pin::Pin,
+  sync::Arc,
+  task::{Context, Poll},
+};
+
+use reqwest::{
+  header::{
+    HeaderMap, HeaderName, HeaderValue, CONNECTION, CONTENT_LENGTH, DATE, TRANSFER_ENCODING,
+  },
+  Request, Response, StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use tower::{Service as TowerService, ServiceExt};
+
+use crate::{
+  middleware::{BoxError, HttpService},
+  Service,
+};
+
+/// Headers which are not recorded, as they describe
+/// the connection rather than the response.
+const SKIPPED_HEADERS: [HeaderName; 4] = [CONNECTION, CONTENT_LENGTH, DATE, TRANSFER_ENCODING];
+
+/// How a [`Cassette`] treats requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CassetteMode {
+  /// Sends every request and records its response,
+  /// replacing whatever the cassette held before.
+  Record,
+  /// Answers requests with their recorded response, without
+  /// any network. Requests which were not recorded fail.
+  Replay,
+  /// Answers requests with their recorded response, and sends
+  /// the others, adding their response to the cassette.
+  RecordNew,
+}
+
+/// A file of recorded HTTP responses, set with
+/// [`MurrayBuilder::cassette`](crate::MurrayBuilder::cassette), so
+/// that integration tests can be recorded against the real
+/// services once and then replayed deterministically.
+///
+/// Requests are matched on their service, method, path, query
+/// and body, so the cassette can be replayed against other base
+/// URLs than the ones it was recorded with. The latest response
+/// of each request is kept, and the file is written after each
+/// recording.
+///
+/// Request headers are never recorded. Other sensitive values,
+/// such as addresses or tokens found in paths or bodies, can be
+/// [scrubbed](Self::scrub) out of the file.
+///
+/// ```no_run
+/// use murray_rs::{Cassette, CassetteMode, Murray};
+///
+/// let cassette = Cassette::open("tests/cassettes/wallet.json", CassetteMode::Replay)
+///   .unwrap()
+///   .scrub("bc1qexampleaddress", "<ADDRESS>");
+///
+/// let murray = Murray::builder().cassette(cassette).build().unwrap();
+/// ```
+///
+#[derive(Clone)]
+pub struct Cassette {
+  path: PathBuf,
+  mode: CassetteMode,
+  scrubs: Vec<(String, String)>,
+  interactions: Arc<tokio::sync::Mutex<Vec<Interaction>>>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct CassetteFile {
+  interactions: Vec<Interaction>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct Interaction {
+  request: RecordedRequest,
+  response: RecordedResponse,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
+struct RecordedRequest {
+  service: String,
+  method: String,
+  path: String,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  body: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct RecordedResponse {
+  status: u16,
+  #[serde(default)]
+  headers: BTreeMap<String, String>,
+  body: String,
+}
+
+impl Cassette {
+  /// Opens the cassette at `path`. It must exist to be replayed,
+  /// while it is created (along with its directory) when recording.
+  ///
+  pub fn open(path: impl AsRef<Path>, mode: CassetteMode) -> io::Result<Self> {
+    let path = path.as_ref().to_path_buf();
+
+    let interactions = match mode {
+      CassetteMode::Record => vec![],
+      CassetteMode::Replay => read(&path)?,
+      CassetteMode::RecordNew => match read(&path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+        interactions => interactions?,
+      },
+    };
+
+    Ok(Self {
+      path,
+      mode,
+      scrubs: vec![],
+      interactions: Arc::new(tokio::sync::Mutex::new(interactions)),
+    })
+  }
+
+  /// Replaces `value` with `placeholder` in what is recorded
+  /// (paths, queries, bodies and response headers), and back
+  /// when replaying, so that responses still mention `value`.
+  ///
+  pub fn scrub(mut self, value: impl Into<String>, placeholder: impl Into<String>) -> Self {
+    let value = value.into();
+    if !value.is_empty() {
+      self.scrubs.push((value, placeholder.into()));
+    }
+    self
+  }
+
+  /// Wraps the stack of `service`, so that its requests
+  /// are recorded or replayed.
+  pub(crate) fn wrap(&self, service: Service, inner: HttpService) -> HttpService {
+    HttpService::new(CassetteService {
+      cassette: self.clone(),
+      service,
+      inner,
+    })
+  }
+
+  async fn call(
+    &self,
+    service: Service,
+    request: Request,
+    inner: HttpService,
+  ) -> Result<Response, BoxError> {
+    let recorded = self.recorded_request(service, &request);
+
+    if self.mode != CassetteMode::Record {
+      let interactions = self.interactions.lock().await;
+      if let Some(interaction) = interactions.iter().find(|i| i.request == recorded) {
+        return self.replay(&interaction.response);
+      }
+    }
+
+    if self.mode == CassetteMode::Replay {
+      return Err(
+        format!(
+          "no recorded response for {} {} {} in {}",
+          recorded.service,
+          recorded.method,
+          recorded.path,
+          self.path.display()
+        )
+        .into(),
+      );
+    }
+
+    let response = inner.oneshot(request).await?;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.bytes().await?;
+
+    self
+      .record(Interaction {
+        request: recorded,
+        response: RecordedResponse {
+          status: status.as_u16(),
+          headers: headers
+            .iter()
+            .filter(|(name, _)| !SKIPPED_HEADERS.contains(name))
+            .filter_map(|(name, value)| {
+              let value = self.scrubbed(value.to_str().ok()?);
+              Some((name.to_string(), value))
+            })
+            .collect(),
+          body: self.scrubbed(&String::from_utf8_lossy(&body)),
+        },
+      })
+      .await?;
+
+    let mut response = http::Response::new(body);
+    *response.status_mut() = status;
+    *response.headers_mut() = headers;
+    Ok(response.into())
+  }
+
+  fn recorded_request(&self, service: Service, request: &Request) -> RecordedRequest {
+    let url = request.url();
+    let path = match url.query() {
+      Some(query) => format!("{}?{}", url.path(), query),
+      None => url.path().to_string(),
+    };
+    let body = request
+      .body()
+      .and_then(|body| body.as_bytes())
+      .map(|body| self.scrubbed(&String::from_utf8_lossy(body)));
+
+    RecordedRequest {
+      service: service.to_string(),
+      method: request.method().to_string(),
+      path: self.scrubbed(&path),
+      body,
+    }
+  }
+
+  fn replay(&self, recorded: &RecordedResponse) -> Result<Response, BoxError> {
+    let mut headers = HeaderMap::new();
+    for (name, value) in &recorded.headers {
+      headers.insert(
+        HeaderName::try_from(name.as_str())?,
+        HeaderValue::try_from(self.unscrubbed(value))?,
+      );
+    }
+
+    let mut response = http::Response::new(self.unscrubbed(&recorded.body));
+    *response.status_mut() = StatusCode::from_u16(recorded.status)?;
+    *response.headers_mut() = headers;
+    Ok(response.into())
+  }
+
+  /// Adds `interaction` to the cassette, replacing the previous
+  /// response to the same request, and writes the cassette.
+  async fn record(&self, interaction: Interaction) -> io::Result<()> {
+    let mut interactions = self.interactions.lock().await;
+    match interactions
+      .iter_mut()
+      .find(|i| i.request == interaction.request)
+    {
+      Some(recorded) => *recorded = interaction,
+      None => interactions.push(interaction),
+    }
+
+    let file = CassetteFile {
+      interactions: interactions.clone(),
+    };
+    let json = serde_json::to_vec_pretty(&file)?;
+    if let Some(dir) = self.path.parent() {
+      tokio::fs::create_dir_all(dir).await?;
+    }
+    tokio::fs::write(&self.path, json).await
+  }
+
+  fn scrubbed(&self, text: &str) -> String {
+    self
+      .scrubs
+      .iter()
+      .fold(text.to_string(), |text, (value, placeholder)| {
+        text.replace(value, placeholder)
+      })
+  }
+
+  fn unscrubbed(&self, text: &str) -> String {
+    self
+      .scrubs
+      .iter()
+      .fold(text.to_string(), |text, (value, placeholder)| {
+        text.replace(placeholder, value)
+      })
+  }
+}
+
+fn read(path: &Path) -> io::Result<Vec<Interaction>> {
+  let json = std::fs::read(path)?;
+  let file: CassetteFile = serde_json::from_slice(&json)?;
+  Ok(file.interactions)
+}
+
+/// Records or replays the requests of a service.
+#[derive(Clone)]
+struct CassetteService {
+  cassette: Cassette,
+  service: Service,
+  inner: HttpService,
+}
+
+impl TowerService<Request> for CassetteService {
+  type Response = Response;
+  type Error = BoxError;
+  type Future = Pin<Box<dyn Future<Output = Result<Response, BoxError>> + Send>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, request: Request) -> Self::Future {
+    let cassette = self.cassette.clone();
+    let service = self.service;
+    let inner = self.inner.clone();
+    Box::pin(async move { cassette.call(service, request, inner).await })
+  }
+}
@@ -0,0 +1,188 @@
+use std::{
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+/// State of the circuit breaker of a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CircuitState {
+  /// Calls are sent as usual.
+  Closed,
+  /// The service is deemed down: calls fail right away
+  /// with an [`ErrorKind::CircuitOpen`](crate::ErrorKind::CircuitOpen) error.
+  Open,
+  /// The service is being probed with its `/health` endpoint
+  /// to find out whether it is back up.
+  HalfOpen,
+}
+
+/// Stops sending calls to a service that keeps failing,
+/// so that they fail right away instead of each waiting
+/// for a timeout.
+///
+/// After `failure_threshold` calls in a row failed with a
+/// connection error, a timeout or a `5xx`/`429` status (once
+/// retries are exhausted), the circuit opens for `open_duration`.
+/// The next call then probes the `/health` endpoint of the
+/// service: the circuit closes again if it answers, and
+/// reopens otherwise.
+///
+/// ```
+/// use std::time::Duration;
+/// use murray_rs::{CircuitBreakerConfig, Murray, Service};
+///
+/// let murray = Murray::builder()
+///   .circuit_breaker(
+///     Service::Lightning,
+///     CircuitBreakerConfig::default()
+///       .failure_threshold(3)
+///       .open_duration(Duration::from_secs(10)),
+///   )
+///   .build()
+///   .unwrap();
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+  failure_threshold: u32,
+  open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+  fn default() -> Self {
+    Self {
+      failure_threshold: 5,
+      open_duration: Duration::from_secs(30),
+    }
+  }
+}
+
+impl CircuitBreakerConfig {
+  /// How many calls in a row must fail to open the circuit (default 5).
+  ///
+  pub fn failure_threshold(mut self, failure_threshold: u32) -> Self {
+    self.failure_threshold = failure_threshold.max(1);
+    self
+  }
+
+  /// How long the circuit stays open before the service
+  /// is probed again (default 30 seconds).
+  ///
+  pub fn open_duration(mut self, open_duration: Duration) -> Self {
+    self.open_duration = open_duration;
+    self
+  }
+}
+
+/// What to do with a call, as decided by [`CircuitBreaker::admit`].
+pub(crate) enum Admission {
+  Allowed,
+  /// Probe the service first, then report with
+  /// [`CircuitBreaker::record_probe`].
+  Probe,
+  /// Fail the call, telling to try again after this long.
+  Rejected(Duration),
+}
+
+/// The circuit breaker enforcing a [`CircuitBreakerConfig`],
+/// shared by all clones of a service.
+pub(crate) struct CircuitBreaker {
+  config: CircuitBreakerConfig,
+  circuit: Mutex<Circuit>,
+}
+
+struct Circuit {
+  state: CircuitState,
+  /// Calls failed in a row while closed.
+  failures: u32,
+  opened_at: Instant,
+}
+
+impl CircuitBreaker {
+  pub fn new(config: CircuitBreakerConfig) -> Self {
+    let circuit = Circuit {
+      state: CircuitState::Closed,
+      failures: 0,
+      opened_at: Instant::now(),
+    };
+
+    Self {
+      config,
+      circuit: Mutex::new(circuit),
+    }
+  }
+
+  fn circuit(&self) -> std::sync::MutexGuard<'_, Circuit> {
+    self.circuit.lock().unwrap_or_else(|e| e.into_inner())
+  }
+
+  pub fn config(&self) -> &CircuitBreakerConfig {
+    &self.config
+  }
+
+  pub fn state(&self) -> CircuitState {
+    self.circuit().state
+  }
+
+  pub fn admit(&self) -> Admission {
+    let mut circuit = self.circuit();
+
+    match circuit.state {
+      CircuitState::Closed => Admission::Allowed,
+      CircuitState::Open => {
+        let open_for = circuit.opened_at.elapsed();
+        if open_for < self.config.open_duration {
+          return Admission::Rejected(self.config.open_duration - open_for);
+        }
+
+        // only this call probes, the others are rejected meanwhile
+        circuit.state = CircuitState::HalfOpen;
+        circuit.opened_at = Instant::now();
+        Admission::Probe
+      }
+      CircuitState::HalfOpen => {
+        // the probing call may have been cancelled
+        if circuit.opened_at.elapsed() >= self.config.open_duration {
+          circuit.opened_at = Instant::now();
+          return Admission::Probe;
+        }
+        Admission::Rejected(Duration::ZERO)
+      }
+    }
+  }
+
+  pub fn open_duration(&self) -> Duration {
+    self.config.open_duration
+  }
+
+  pub fn record_probe(&self, healthy: bool) {
+    let mut circuit = self.circuit();
+    if healthy {
+      circuit.state = CircuitState::Closed;
+      circuit.failures = 0;
+    } else {
+      circuit.state = CircuitState::Open;
+      circuit.opened_at = Instant::now();
+    }
+  }
+
+  /// Records the outcome of a call sent while the circuit was closed.
+  pub fn record(&self, failed: bool) {
+    let mut circuit = self.circuit();
+    if circuit.state != CircuitState::Closed {
+      return;
+    }
+
+    if !failed {
+      circuit.failures = 0;
+      return;
+    }
+
+    circuit.failures += 1;
+    if circuit.failures >= self.config.failure_threshold {
+      circuit.state = CircuitState::Open;
+      circuit.opened_at = Instant::now();
+    }
+  }
+}
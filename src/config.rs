@@ -0,0 +1,410 @@
+//! Configuration of a [`MurrayBuilder`] from environment
+//! variables or, with the `config-file` feature, a file.
+
+use std::{env, fmt, path::PathBuf, time::Duration};
+
+use reqwest::Url;
+use serde::{de, Deserialize, Deserializer};
+
+use crate::{Auth, BuilderError, MurrayBuilder, RetryPolicy, Service};
+
+const SERVICES: [Service; 3] = [Service::Blockchain, Service::Prices, Service::Lightning];
+
+const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+
+/// Error returned when a [`Murray`](crate::Murray) can't be
+/// configured from environment variables or a config file.
+#[derive(thiserror::Error, Debug)]
+pub enum ConfigError {
+  /// The value of a setting is invalid. `key` is the name of the
+  /// environment variable, or the dotted path of the key in the
+  /// config file (e.g. `blockchain.fallback_urls[1]`).
+  #[error("invalid `{key}`: {message}")]
+  Invalid { key: String, message: String },
+  #[error("could not read `{path}`: {source}")]
+  Read {
+    path: PathBuf,
+    source: std::io::Error,
+  },
+  /// The config file is not valid TOML or YAML.
+  #[error("could not parse `{path}`: {message}")]
+  Parse { path: PathBuf, message: String },
+  #[error("unsupported config file `{0}`, expected a .toml, .yaml or .yml file")]
+  UnsupportedFormat(PathBuf),
+  #[error(transparent)]
+  Builder(#[from] BuilderError),
+}
+
+impl ConfigError {
+  /// The environment variable or config file key
+  /// with an invalid value, if that is the error.
+  pub fn key(&self) -> Option<&str> {
+    match self {
+      ConfigError::Invalid { key, .. } => Some(key),
+      _ => None,
+    }
+  }
+
+  fn invalid(key: impl Into<String>, message: impl fmt::Display) -> Self {
+    ConfigError::Invalid {
+      key: key.into(),
+      message: message.to_string(),
+    }
+  }
+}
+
+/// Every setting that can be configured, as read from
+/// the config file, or from the environment variables.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct Config {
+  #[serde(deserialize_with = "de_duration")]
+  timeout: Option<Duration>,
+  #[serde(deserialize_with = "de_duration")]
+  connect_timeout: Option<Duration>,
+  user_agent: Option<String>,
+  retry: RetryConfig,
+  blockchain: ServiceConfig,
+  prices: ServiceConfig,
+  lightning: ServiceConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RetryConfig {
+  max_attempts: Option<u32>,
+  #[serde(deserialize_with = "de_duration")]
+  initial_backoff: Option<Duration>,
+  #[serde(deserialize_with = "de_duration")]
+  max_backoff: Option<Duration>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ServiceConfig {
+  #[serde(deserialize_with = "de_url")]
+  url: Option<String>,
+  #[serde(deserialize_with = "de_urls")]
+  fallback_urls: Vec<String>,
+  auth: AuthConfig,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct AuthConfig {
+  bearer_token: Option<String>,
+  api_key: Option<String>,
+  api_key_header: Option<String>,
+}
+
+impl fmt::Debug for AuthConfig {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("AuthConfig")
+      .field(
+        "bearer_token",
+        &self.bearer_token.as_ref().map(|_| "<redacted>"),
+      )
+      .field("api_key", &self.api_key.as_ref().map(|_| "<redacted>"))
+      .field("api_key_header", &self.api_key_header)
+      .finish()
+  }
+}
+
+impl Config {
+  /// Reads the `MURRAY_*` environment variables.
+  pub fn from_env() -> Result<Self, ConfigError> {
+    Self::from_vars(|name| env::var(name).ok())
+  }
+
+  fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, ConfigError> {
+    let duration = |name: &str| parse_var(name, var(name), parse_duration);
+
+    let mut config = Config {
+      timeout: duration("MURRAY_TIMEOUT")?,
+      connect_timeout: duration("MURRAY_CONNECT_TIMEOUT")?,
+      user_agent: var("MURRAY_USER_AGENT"),
+      retry: RetryConfig {
+        max_attempts: parse_var(
+          "MURRAY_RETRY_MAX_ATTEMPTS",
+          var("MURRAY_RETRY_MAX_ATTEMPTS"),
+          parse_attempts,
+        )?,
+        initial_backoff: duration("MURRAY_RETRY_INITIAL_BACKOFF")?,
+        max_backoff: duration("MURRAY_RETRY_MAX_BACKOFF")?,
+      },
+      ..Default::default()
+    };
+
+    for service in SERVICES {
+      let prefix = format!("MURRAY_{}", service.to_string().to_uppercase());
+      let name = |setting: &str| format!("{}_{}", prefix, setting);
+
+      let fallbacks_name = name("FALLBACK_URLS");
+      let fallback_urls = match var(&fallbacks_name) {
+        Some(urls) => urls
+          .split(',')
+          .map(str::trim)
+          .filter(|url| !url.is_empty())
+          .map(|url| parse_url(url).map_err(|e| ConfigError::invalid(&fallbacks_name, e)))
+          .collect::<Result<_, _>>()?,
+        None => vec![],
+      };
+
+      *config.service_mut(service) = ServiceConfig {
+        url: parse_var(&name("URL"), var(&name("URL")), parse_url)?,
+        fallback_urls,
+        auth: AuthConfig {
+          bearer_token: var(&name("BEARER_TOKEN")),
+          api_key: var(&name("API_KEY")),
+          api_key_header: var(&name("API_KEY_HEADER")),
+        },
+      };
+    }
+
+    Ok(config)
+  }
+
+  /// Reads a TOML or YAML file, depending on its extension.
+  #[cfg(feature = "config-file")]
+  pub fn from_file(path: &std::path::Path) -> Result<Self, ConfigError> {
+    let read = |path: &std::path::Path| {
+      std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+      })
+    };
+    let parse_error = |message: String| ConfigError::Parse {
+      path: path.to_path_buf(),
+      message,
+    };
+
+    let extension = path.extension().and_then(|extension| extension.to_str());
+    let result = match extension {
+      Some("toml") => {
+        let text = read(path)?;
+        let deserializer = toml::Deserializer::new(&text);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| {
+          let path = e.path().to_string();
+          (path, e.into_inner().message().to_string())
+        })
+      }
+      Some("yaml" | "yml") => {
+        let text = read(path)?;
+        let deserializer = serde_yaml::Deserializer::from_str(&text);
+        serde_path_to_error::deserialize(deserializer).map_err(|e| {
+          let path = e.path().to_string();
+          (path, e.into_inner().to_string())
+        })
+      }
+      _ => return Err(ConfigError::UnsupportedFormat(path.to_path_buf())),
+    };
+
+    result.map_err(|(key, message)| {
+      // errors outside of any key are syntax errors
+      if key == "." {
+        parse_error(message)
+      } else {
+        ConfigError::invalid(key, message)
+      }
+    })
+  }
+
+  fn service_mut(&mut self, service: Service) -> &mut ServiceConfig {
+    match service {
+      Service::Blockchain => &mut self.blockchain,
+      Service::Prices => &mut self.prices,
+      Service::Lightning => &mut self.lightning,
+    }
+  }
+
+  /// Applies the settings that were set on top of `builder`.
+  /// `key` names an auth setting of a service, for errors.
+  pub fn apply(
+    mut self,
+    mut builder: MurrayBuilder,
+    key: impl Fn(Service, &str) -> String,
+  ) -> Result<MurrayBuilder, ConfigError> {
+    if let Some(timeout) = self.timeout {
+      builder = builder.timeout(timeout);
+    }
+    if let Some(timeout) = self.connect_timeout {
+      builder = builder.connect_timeout(timeout);
+    }
+    if let Some(user_agent) = self.user_agent.take() {
+      builder = builder.user_agent(user_agent);
+    }
+
+    let RetryConfig {
+      max_attempts,
+      initial_backoff,
+      max_backoff,
+    } = self.retry;
+    if max_attempts.is_some() || initial_backoff.is_some() || max_backoff.is_some() {
+      let mut retry_policy = RetryPolicy::default();
+      if let Some(max_attempts) = max_attempts {
+        retry_policy = retry_policy.max_attempts(max_attempts);
+      }
+      if let Some(backoff) = initial_backoff {
+        retry_policy = retry_policy.initial_backoff(backoff);
+      }
+      if let Some(backoff) = max_backoff {
+        retry_policy = retry_policy.max_backoff(backoff);
+      }
+      builder = builder.retry_policy(retry_policy);
+    }
+
+    for service in SERVICES {
+      let config = std::mem::take(self.service_mut(service));
+
+      builder = match (service, config.url) {
+        (_, None) => builder,
+        (Service::Blockchain, Some(url)) => builder.blockchain_endpoint(url),
+        (Service::Prices, Some(url)) => builder.prices_endpoint(url),
+        (Service::Lightning, Some(url)) => builder.lightning_endpoint(url),
+      };
+      if !config.fallback_urls.is_empty() {
+        builder = builder.fallback_endpoints(service, config.fallback_urls);
+      }
+
+      let AuthConfig {
+        bearer_token,
+        api_key,
+        api_key_header,
+      } = config.auth;
+      if api_key_header.is_some() && api_key.is_none() {
+        return Err(ConfigError::invalid(
+          key(service, "api_key_header"),
+          format!("can't be set without `{}`", key(service, "api_key")),
+        ));
+      }
+      let auth = match (bearer_token, api_key) {
+        (Some(_), Some(_)) => {
+          return Err(ConfigError::invalid(
+            key(service, "api_key"),
+            format!("can't be set along with `{}`", key(service, "bearer_token")),
+          ))
+        }
+        (Some(token), None) => Some(Auth::bearer(token)),
+        (None, Some(api_key)) => {
+          let header = api_key_header.unwrap_or_else(|| DEFAULT_API_KEY_HEADER.to_string());
+          let auth = Auth::api_key(&header, api_key).map_err(|_| {
+            ConfigError::invalid(
+              key(service, "api_key_header"),
+              format!("`{}` is not a valid header name", header),
+            )
+          })?;
+          Some(auth)
+        }
+        (None, None) => None,
+      };
+      if let Some(auth) = auth {
+        builder = builder.auth(service, auth);
+      }
+    }
+
+    Ok(builder)
+  }
+}
+
+/// Parses the `value` of the environment variable `name`, if set.
+fn parse_var<T>(
+  name: &str,
+  value: Option<String>,
+  parse: fn(&str) -> Result<T, String>,
+) -> Result<Option<T>, ConfigError> {
+  value
+    .map(|value| parse(value.trim()).map_err(|e| ConfigError::invalid(name, e)))
+    .transpose()
+}
+
+fn parse_attempts(value: &str) -> Result<u32, String> {
+  value
+    .parse()
+    .map_err(|_| format!("expected a number of attempts, got `{}`", value))
+}
+
+/// Parses `500ms`, `30s`, `5m`, `1h`, or a number of seconds.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+  let invalid = || {
+    format!(
+      "expected a duration such as `30s`, `500ms` or `5m`, got `{}`",
+      value
+    )
+  };
+  let split = value
+    .find(|c: char| !c.is_ascii_digit() && c != '.')
+    .unwrap_or(value.len());
+  let (amount, unit) = value.split_at(split);
+  let amount: f64 = amount.parse().map_err(|_| invalid())?;
+
+  let seconds = match unit.trim() {
+    "ms" => amount / 1000.0,
+    "" | "s" => amount,
+    "m" => amount * 60.0,
+    "h" => amount * 3600.0,
+    _ => return Err(invalid()),
+  };
+  Duration::try_from_secs_f64(seconds).map_err(|_| invalid())
+}
+
+/// Checks that `value` is an HTTP(S) base URL.
+fn parse_url(value: &str) -> Result<String, String> {
+  let url = Url::parse(value).map_err(|e| format!("`{}` is not a valid URL: {}", value, e))?;
+  if !matches!(url.scheme(), "http" | "https") {
+    return Err(format!("`{}` is not an HTTP(S) URL", value));
+  }
+  Ok(value.trim_end_matches('/').to_string())
+}
+
+fn de_duration<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+  struct DurationVisitor;
+
+  impl de::Visitor<'_> for DurationVisitor {
+    type Value = Duration;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      f.write_str("a duration such as `30s`, `500ms` or `5m`, or a number of seconds")
+    }
+
+    fn visit_u64<E: de::Error>(self, seconds: u64) -> Result<Duration, E> {
+      Ok(Duration::from_secs(seconds))
+    }
+
+    fn visit_i64<E: de::Error>(self, seconds: i64) -> Result<Duration, E> {
+      u64::try_from(seconds)
+        .map(Duration::from_secs)
+        .map_err(|_| E::invalid_value(de::Unexpected::Signed(seconds), &self))
+    }
+
+    fn visit_f64<E: de::Error>(self, seconds: f64) -> Result<Duration, E> {
+      Duration::try_from_secs_f64(seconds)
+        .map_err(|_| E::invalid_value(de::Unexpected::Float(seconds), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Duration, E> {
+      parse_duration(value).map_err(E::custom)
+    }
+  }
+
+  deserializer.deserialize_any(DurationVisitor).map(Some)
+}
+
+fn de_url<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+  let url = String::deserialize(deserializer)?;
+  parse_url(&url).map(Some).map_err(de::Error::custom)
+}
+
+fn de_urls<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+  /// Validates each URL on its own, so that errors point at its index.
+  struct Url(String);
+
+  impl<'de> Deserialize<'de> for Url {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+      let url = String::deserialize(deserializer)?;
+      parse_url(&url).map(Url).map_err(de::Error::custom)
+    }
+  }
+
+  let urls = Vec::<Url>::deserialize(deserializer)?;
+  Ok(urls.into_iter().map(|Url(url)| url).collect())
+}
@@ -0,0 +1,352 @@
+use std::{error::Error as StdError, fmt, result, sync::Arc, time::Duration};
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::Service;
+
+pub type Result<T> = result::Result<T, MurrayError>;
+
+type Source = Arc<dyn StdError + Send + Sync>;
+
+/// The category of a [`MurrayError`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+  /// The request URL could not be built from the given params.
+  InvalidURLParams,
+  /// The request never got an HTTP response
+  /// (connection refused, timeout, TLS failure...).
+  Request,
+  /// The service answered with a non-success HTTP status.
+  API,
+  /// The response body could not be decoded.
+  JSONParse,
+  /// The call was rejected by the client-side [`RateLimit`](crate::RateLimit)
+  /// without being sent.
+  RateLimited,
+  /// The call was rejected without being sent, because the
+  /// [circuit breaker](crate::CircuitBreakerConfig) of the service is open.
+  CircuitOpen,
+  /// The credentials of the service could not be obtained from
+  /// its [token provider](crate::Auth::token_provider).
+  Auth,
+  /// The call was dropped when its [cancellation
+  /// token](crate::RequestOptions::cancellation_token) got cancelled.
+  Cancelled,
+  /// The call was dropped as it was still running at its
+  /// [deadline](crate::RequestOptions::deadline).
+  DeadlineExceeded,
+}
+
+impl fmt::Display for ErrorKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let kind = match self {
+      ErrorKind::InvalidURLParams => "Invalid URL params",
+      ErrorKind::Request => "Request error",
+      ErrorKind::API => "API error",
+      ErrorKind::JSONParse => "JSON parse error",
+      ErrorKind::RateLimited => "Rate limited",
+      ErrorKind::CircuitOpen => "Circuit open",
+      ErrorKind::Auth => "Authentication error",
+      ErrorKind::Cancelled => "Cancelled",
+      ErrorKind::DeadlineExceeded => "Deadline exceeded",
+    };
+    f.write_str(kind)
+  }
+}
+
+/// Error returned by every call made through [`Murray`](crate::Murray).
+///
+/// Besides its [`ErrorKind`], it records which [`Service`] and
+/// endpoint were being called, the HTTP status and error message
+/// returned by the service (if any), and the underlying
+/// `reqwest`/`serde_json`/`url` error, available via
+/// [`std::error::Error::source`].
+///
+/// It is cheap to clone, as the source is reference counted.
+///
+#[derive(Clone)]
+pub struct MurrayError {
+  kind: ErrorKind,
+  service: Service,
+  endpoint: &'static str,
+  status: Option<StatusCode>,
+  message: Option<String>,
+  body: Option<Value>,
+  retry_after: Option<Duration>,
+  source: Option<Source>,
+}
+
+impl MurrayError {
+  pub(crate) fn new(kind: ErrorKind, service: Service, endpoint: &'static str) -> Self {
+    Self {
+      kind,
+      service,
+      endpoint,
+      status: None,
+      message: None,
+      body: None,
+      retry_after: None,
+      source: None,
+    }
+  }
+
+  pub(crate) fn with_source<E>(mut self, source: E) -> Self
+  where
+    E: StdError + Send + Sync + 'static,
+  {
+    self.source = Some(Arc::new(source));
+    self
+  }
+
+  pub(crate) fn with_boxed_source(mut self, source: Box<dyn StdError + Send + Sync>) -> Self {
+    self.source = Some(Arc::from(source));
+    self
+  }
+
+  pub(crate) fn with_retry_after(mut self, retry_after: Option<Duration>) -> Self {
+    self.retry_after = retry_after;
+    self
+  }
+
+  /// Replaces `secret` with `<redacted>` in the message and body.
+  pub(crate) fn redact(mut self, secret: &str) -> Self {
+    if secret.is_empty() {
+      return self;
+    }
+    if let Some(message) = &mut self.message {
+      *message = message.replace(secret, REDACTED);
+    }
+    if let Some(body) = &mut self.body {
+      redact_value(body, secret);
+    }
+    self
+  }
+
+  /// Builds an error of `kind`, as returned when calling `endpoint`
+  /// (e.g. `/tx/{txid}`) of `service`, to be returned by [fakes](crate::fake).
+  #[cfg(feature = "test-utils")]
+  pub fn fake(kind: ErrorKind, service: Service, endpoint: &'static str) -> Self {
+    Self::new(kind, service, endpoint)
+  }
+
+  /// Builds an [`ErrorKind::API`] error, as returned when `endpoint`
+  /// of `service` answers with `status` and `body`, to be returned
+  /// by [fakes](crate::fake).
+  #[cfg(feature = "test-utils")]
+  pub fn fake_api(
+    service: Service,
+    endpoint: &'static str,
+    status: StatusCode,
+    body: impl AsRef<[u8]>,
+  ) -> Self {
+    Self::api(service, endpoint, status, body.as_ref())
+  }
+
+  /// Builds an [`ErrorKind::API`] error out of a non-success
+  /// response, extracting the service's error message from its body.
+  pub(crate) fn api(
+    service: Service,
+    endpoint: &'static str,
+    status: StatusCode,
+    body: &[u8],
+  ) -> Self {
+    let mut error = Self::new(ErrorKind::API, service, endpoint);
+    error.status = Some(status);
+
+    match serde_json::from_slice::<Value>(body) {
+      Ok(json) => {
+        error.message = error_message(&json);
+        error.body = Some(json);
+      }
+      Err(_) => {
+        let text = String::from_utf8_lossy(body);
+        let text = text.trim();
+        if !text.is_empty() {
+          error.message = Some(text.chars().take(MAX_MESSAGE_LEN).collect());
+        }
+      }
+    }
+
+    error
+  }
+
+  pub fn kind(&self) -> ErrorKind {
+    self.kind
+  }
+
+  /// The service that was being called.
+  pub fn service(&self) -> Service {
+    self.service
+  }
+
+  /// The endpoint that was being called, as a route
+  /// template such as `/tx/{txid}`.
+  pub fn endpoint(&self) -> &'static str {
+    self.endpoint
+  }
+
+  /// The HTTP status returned by the service, if a response was received.
+  pub fn status(&self) -> Option<StatusCode> {
+    self.status
+  }
+
+  /// The error message returned by the service, if any.
+  pub fn message(&self) -> Option<&str> {
+    self.message.as_deref()
+  }
+
+  /// The JSON body of the service's error response, if any.
+  pub fn body(&self) -> Option<&Value> {
+    self.body.as_ref()
+  }
+
+  /// How long the service asked to wait before trying
+  /// again, from its `Retry-After` header.
+  pub fn retry_after(&self) -> Option<Duration> {
+    self.retry_after
+  }
+
+  /// `true` if the service answered with `404 Not Found`,
+  /// e.g. for an unknown txid or address.
+  pub fn is_not_found(&self) -> bool {
+    self.status == Some(StatusCode::NOT_FOUND)
+  }
+
+  /// `true` if the service answered with `429 Too Many Requests`,
+  /// or the call was rejected by the client-side [`RateLimit`](crate::RateLimit).
+  pub fn is_rate_limited(&self) -> bool {
+    self.kind == ErrorKind::RateLimited || self.status == Some(StatusCode::TOO_MANY_REQUESTS)
+  }
+
+  /// `true` if the call was rejected because the service
+  /// is deemed down by its circuit breaker.
+  pub fn is_circuit_open(&self) -> bool {
+    self.kind == ErrorKind::CircuitOpen
+  }
+
+  /// `true` if the request timed out before a response was
+  /// received, or the call was still running at its deadline.
+  pub fn is_timeout(&self) -> bool {
+    self.kind == ErrorKind::DeadlineExceeded
+      || self
+        .reqwest_source()
+        .map(reqwest::Error::is_timeout)
+        .unwrap_or(false)
+  }
+
+  /// `true` if the same request may succeed when sent again:
+  /// connection failures, timeouts, `408`, `429` and `5xx`
+  /// statuses other than `501 Not Implemented`, and calls
+  /// rejected by the client-side rate limiter or circuit breaker.
+  pub fn is_retryable(&self) -> bool {
+    match self.kind {
+      ErrorKind::RateLimited | ErrorKind::CircuitOpen => true,
+      ErrorKind::Request => self
+        .reqwest_source()
+        .map(|e| e.is_timeout() || e.is_connect() || e.is_request())
+        .unwrap_or(false),
+      ErrorKind::API => self.status.map(is_retryable_status).unwrap_or(false),
+      ErrorKind::InvalidURLParams
+      | ErrorKind::JSONParse
+      | ErrorKind::Auth
+      | ErrorKind::Cancelled
+      | ErrorKind::DeadlineExceeded => false,
+    }
+  }
+
+  fn reqwest_source(&self) -> Option<&reqwest::Error> {
+    self.source.as_deref()?.downcast_ref::<reqwest::Error>()
+  }
+}
+
+/// Longest error message kept from a non-JSON error body.
+const MAX_MESSAGE_LEN: usize = 512;
+
+const REDACTED: &str = "<redacted>";
+
+fn redact_value(value: &mut Value, secret: &str) {
+  match value {
+    Value::String(s) if s.contains(secret) => *s = s.replace(secret, REDACTED),
+    Value::Array(items) => items.iter_mut().for_each(|item| redact_value(item, secret)),
+    Value::Object(map) => map.values_mut().for_each(|item| redact_value(item, secret)),
+    _ => {}
+  }
+}
+
+pub(crate) fn is_retryable_status(status: StatusCode) -> bool {
+  matches!(
+    status,
+    StatusCode::REQUEST_TIMEOUT | StatusCode::TOO_MANY_REQUESTS
+  ) || (status.is_server_error() && status != StatusCode::NOT_IMPLEMENTED)
+}
+
+/// Murray services answer errors with `{ "message": ... }`,
+/// where the message may be either a string or a list of strings.
+fn error_message(json: &Value) -> Option<String> {
+  let message = json.get("message").or_else(|| json.get("error"))?;
+
+  match message {
+    Value::String(s) => Some(s.clone()),
+    Value::Array(items) => Some(
+      items
+        .iter()
+        .map(|item| match item {
+          Value::String(s) => s.clone(),
+          other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("; "),
+    ),
+    Value::Null => None,
+    other => Some(other.to_string()),
+  }
+}
+
+impl fmt::Debug for MurrayError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut debug = f.debug_struct("MurrayError");
+    debug
+      .field("kind", &self.kind)
+      .field("service", &self.service)
+      .field("endpoint", &self.endpoint);
+    if let Some(status) = self.status {
+      debug.field("status", &status);
+    }
+    if let Some(message) = &self.message {
+      debug.field("message", message);
+    }
+    if let Some(source) = &self.source {
+      debug.field("source", source);
+    }
+    debug.finish()
+  }
+}
+
+impl fmt::Display for MurrayError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{} ({} `{}`", self.kind, self.service, self.endpoint)?;
+    if let Some(status) = self.status {
+      write!(f, ", status {}", status)?;
+    }
+    f.write_str(")")?;
+
+    if let Some(message) = &self.message {
+      write!(f, ": `{}`", message)?;
+    } else if let Some(source) = &self.source {
+      write!(f, ": `{}`", source)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl StdError for MurrayError {
+  fn source(&self) -> Option<&(dyn StdError + 'static)> {
+    self
+      .source
+      .as_deref()
+      .map(|source| source as &(dyn StdError + 'static))
+  }
+}
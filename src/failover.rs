@@ -0,0 +1,256 @@
+use std::{
+  sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, Mutex,
+  },
+  time::{Duration, Instant},
+};
+
+use reqwest::{
+  header::{HeaderValue, ACCEPT},
+  Method, Request, Url,
+};
+use tower::ServiceExt;
+
+use crate::{auth::Auth, middleware::HttpService};
+
+/// How a service with several base URLs picks the one a call is sent to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailoverStrategy {
+  /// The first healthy URL, in the order they were given. Calls
+  /// fail back to a preferred URL as soon as it is healthy again.
+  Priority,
+  /// Healthy URLs take turns.
+  RoundRobin,
+  /// The healthy URL that answered the fastest lately.
+  LowestLatency,
+}
+
+/// Decides how calls are spread over, and fail over between,
+/// the base URLs of a service.
+///
+/// A URL is marked unhealthy when a call to it fails with a
+/// connection error, a timeout or a `5xx`/`429` status. The call is
+/// then sent right away to the next healthy URL, if any (for the
+/// methods the [`RetryPolicy`](crate::RetryPolicy) retries). Every
+/// `probe_interval`, unhealthy URLs are probed with the `/health`
+/// endpoint of the service, and used again once it answers.
+///
+/// When every URL is unhealthy, they are all still tried.
+///
+/// ```
+/// use std::time::Duration;
+/// use murray_rs::{FailoverPolicy, FailoverStrategy, Murray, Service};
+///
+/// let murray = Murray::builder()
+///   .blockchain_endpoint("https://blockchain.example.com")
+///   .fallback_endpoints(Service::Blockchain, ["http://blockchain.murrayrothbot.com"])
+///   .failover_policy(
+///     FailoverPolicy::default()
+///       .strategy(FailoverStrategy::LowestLatency)
+///       .probe_interval(Duration::from_secs(10)),
+///   )
+///   .build()
+///   .unwrap();
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct FailoverPolicy {
+  strategy: FailoverStrategy,
+  probe_interval: Duration,
+}
+
+impl Default for FailoverPolicy {
+  fn default() -> Self {
+    Self {
+      strategy: FailoverStrategy::Priority,
+      probe_interval: Duration::from_secs(30),
+    }
+  }
+}
+
+impl FailoverPolicy {
+  pub fn strategy(mut self, strategy: FailoverStrategy) -> Self {
+    self.strategy = strategy;
+    self
+  }
+
+  /// How often unhealthy URLs are probed (default 30 seconds).
+  ///
+  pub fn probe_interval(mut self, interval: Duration) -> Self {
+    self.probe_interval = interval;
+    self
+  }
+}
+
+/// The base URLs of a service and what is known of their health,
+/// shared by all clones of the service.
+pub(crate) struct Endpoints {
+  policy: FailoverPolicy,
+  endpoints: Vec<Endpoint>,
+  next: AtomicUsize,
+}
+
+struct Endpoint {
+  url: String,
+  health: Mutex<Health>,
+  probing: AtomicBool,
+}
+
+struct Health {
+  healthy: bool,
+  /// Moving average of how long successful calls took.
+  latency: Option<Duration>,
+  checked_at: Instant,
+}
+
+impl Endpoints {
+  pub fn new(urls: Vec<String>, policy: FailoverPolicy) -> Self {
+    let endpoints = urls
+      .into_iter()
+      .map(|url| Endpoint {
+        url,
+        health: Mutex::new(Health {
+          healthy: true,
+          latency: None,
+          checked_at: Instant::now(),
+        }),
+        probing: AtomicBool::new(false),
+      })
+      .collect();
+
+    Self {
+      policy,
+      endpoints,
+      next: AtomicUsize::new(0),
+    }
+  }
+
+  pub fn policy(&self) -> &FailoverPolicy {
+    &self.policy
+  }
+
+  pub fn len(&self) -> usize {
+    self.endpoints.len()
+  }
+
+  pub fn url(&self, index: usize) -> &str {
+    &self.endpoints[index].url
+  }
+
+  fn health(&self, index: usize) -> std::sync::MutexGuard<'_, Health> {
+    self.endpoints[index]
+      .health
+      .lock()
+      .unwrap_or_else(|e| e.into_inner())
+  }
+
+  fn is_healthy(&self, index: usize) -> bool {
+    self.health(index).healthy
+  }
+
+  /// Index of the URL the next call should be sent to,
+  /// skipping the ones in `tried`.
+  pub fn select(&self, tried: &[usize]) -> usize {
+    let candidates: Vec<usize> = (0..self.len()).filter(|i| !tried.contains(i)).collect();
+    let healthy: Vec<usize> = candidates
+      .iter()
+      .copied()
+      .filter(|&i| self.is_healthy(i))
+      .collect();
+    let candidates = match (healthy.is_empty(), candidates.is_empty()) {
+      (false, _) => healthy,
+      (true, false) => candidates,
+      (true, true) => return 0,
+    };
+
+    match self.policy.strategy {
+      FailoverStrategy::Priority => candidates[0],
+      FailoverStrategy::RoundRobin => {
+        let turn = self.next.fetch_add(1, Ordering::Relaxed);
+        candidates[turn % candidates.len()]
+      }
+      FailoverStrategy::LowestLatency => candidates
+        .iter()
+        .copied()
+        // URLs never timed yet are tried first, to learn their latency
+        .min_by_key(|&i| self.health(i).latency.unwrap_or(Duration::ZERO))
+        .unwrap_or(candidates[0]),
+    }
+  }
+
+  /// Whether a URL not in `tried` can still be used.
+  pub fn has_healthy(&self, tried: &[usize]) -> bool {
+    (0..self.len()).any(|i| !tried.contains(&i) && self.is_healthy(i))
+  }
+
+  pub fn record_success(&self, index: usize, elapsed: Duration) {
+    let mut health = self.health(index);
+    health.healthy = true;
+    health.checked_at = Instant::now();
+    health.latency = Some(match health.latency {
+      Some(latency) => (latency * 4 + elapsed) / 5,
+      None => elapsed,
+    });
+  }
+
+  pub fn record_failure(&self, index: usize) {
+    let mut health = self.health(index);
+    health.healthy = false;
+    health.checked_at = Instant::now();
+  }
+
+  /// Probes, in the background, the unhealthy URLs which
+  /// haven't been checked for a `probe_interval`.
+  pub fn probe_unhealthy(self: &Arc<Self>, http: &HttpService, auth: Option<&Auth>) {
+    if self.len() < 2 {
+      return;
+    }
+
+    for index in 0..self.len() {
+      let due = {
+        let health = self.health(index);
+        !health.healthy && health.checked_at.elapsed() >= self.policy.probe_interval
+      };
+      let endpoint = &self.endpoints[index];
+      if !due || endpoint.probing.swap(true, Ordering::AcqRel) {
+        continue;
+      }
+
+      let endpoints = self.clone();
+      let http = http.clone();
+      let auth = auth.cloned();
+      tokio::spawn(async move {
+        match probe_health(&http, auth.as_ref(), endpoints.url(index)).await {
+          Some(elapsed) => endpoints.record_success(index, elapsed),
+          None => endpoints.record_failure(index),
+        }
+        endpoints.endpoints[index]
+          .probing
+          .store(false, Ordering::Release);
+      });
+    }
+  }
+}
+
+/// Calls the `/health` endpoint of the service at `base_url`,
+/// returning how long it took to answer successfully.
+pub(crate) async fn probe_health(
+  http: &HttpService,
+  auth: Option<&Auth>,
+  base_url: &str,
+) -> Option<Duration> {
+  let url = Url::parse(&format!("{}/health", base_url)).ok()?;
+  let mut request = Request::new(Method::GET, url);
+  request
+    .headers_mut()
+    .insert(ACCEPT, HeaderValue::from_static("application/json"));
+  if let Some(auth) = auth {
+    auth.apply(request.headers_mut()).await.ok()?;
+  }
+
+  let started = Instant::now();
+  let response = http.clone().oneshot(request).await.ok()?;
+
+  response.status().is_success().then(|| started.elapsed())
+}
@@ -0,0 +1,149 @@
+//! Fake implementations of [`BlockchainApi`], [`LightningApi`]
+//! and [`PricesApi`], to unit test code depending on them
+//! without spinning up an HTTP server.
+//!
+//! Enabled with the `test-utils` cargo feature, which is meant
+//! for `[dev-dependencies]`. Each call answers with whatever the
+//! closure programmed with the matching `on_*` method returns,
+//! and panics if none was. The responses implement [`Default`],
+//! so that only the fields the tested code reads need to be set:
+//!
+//! ```
+//! use std::sync::Arc;
+//! use murray_rs::{
+//!   fake::FakeBlockchain, transaction::Transaction, BlockchainApi, ErrorKind,
+//!   GetHealthResponse, GetTransactionResponse, MurrayError, Service,
+//! };
+//!
+//! let blockchain: Arc<dyn BlockchainApi> = Arc::new(
+//!   FakeBlockchain::new()
+//!     .on_get_health(|| {
+//!       Ok(GetHealthResponse {
+//!         message: "OK".to_string(),
+//!       })
+//!     })
+//!     .on_get_transaction(|params| {
+//!       Ok(GetTransactionResponse {
+//!         transaction: Transaction {
+//!           txid: params.txid,
+//!           fee: 1_000,
+//!           ..Default::default()
+//!         },
+//!         ..Default::default()
+//!       })
+//!     })
+//!     .on_get_mempool(|| {
+//!       Err(MurrayError::fake(ErrorKind::Request, Service::Blockchain, "/mempool"))
+//!     }),
+//! );
+//! ```
+//!
+use std::sync::Arc;
+
+use async_trait::async_trait;
+
+use crate::{
+  error::Result, node_details::NodeData, statistics::Statistics, top_nodes::TopData, BlockRef,
+  BlockchainApi, ConvertCurrencyParams, ConvertCurrencyReturn, GetAddressDetailsResponse,
+  GetAddressParams, GetAddressTransactionsResponse, GetAddressUTXOResponse, GetBlock2TimeResponse,
+  GetBlockResponse, GetFeesMempoolBlocksResponse, GetFeesRecommendedResponse, GetHashrateResponse,
+  GetHealthResponse, GetMempoolResponse, GetNodeDetailsParams, GetTickerParams, GetTickerReturn,
+  GetTickersReturn, GetTransactionParams, GetTransactionResponse, LightningApi,
+  PostTransactionParams, PostTransactionResponse, PricesApi,
+};
+
+/// Declares a fake implementing `$api`, with one optional
+/// responder per call and an `on_*` method to program it.
+macro_rules! fake {
+  (
+    $(#[$doc:meta])*
+    $fake:ident: $api:ident {
+      $( $method:ident($($param:ident: $param_ty:ty)?) -> $ret:ty => $on:ident; )*
+    }
+  ) => {
+    $(#[$doc])*
+    #[derive(Clone, Default)]
+    pub struct $fake {
+      $( $method: Option<Arc<dyn Fn($($param_ty)?) -> Result<$ret> + Send + Sync>>, )*
+    }
+
+    impl $fake {
+      /// A fake without any programmed response.
+      pub fn new() -> Self {
+        Self::default()
+      }
+
+      $(
+        #[doc = concat!("Answers `", stringify!($method), "` calls with `responder`.")]
+        pub fn $on<F>(mut self, responder: F) -> Self
+        where
+          F: Fn($($param_ty)?) -> Result<$ret> + Send + Sync + 'static,
+        {
+          self.$method = Some(Arc::new(responder));
+          self
+        }
+      )*
+    }
+
+    #[async_trait]
+    impl $api for $fake {
+      $(
+        async fn $method(&self $(, $param: $param_ty)?) -> Result<$ret> {
+          let responder = self.$method.as_ref().unwrap_or_else(|| {
+            panic!(
+              "{}::{} was called without a response, program one with `{}`",
+              stringify!($fake),
+              stringify!($method),
+              stringify!($on),
+            )
+          });
+          responder($($param)?)
+        }
+      )*
+    }
+  };
+}
+
+fake! {
+  /// A programmable [`BlockchainApi`].
+  FakeBlockchain: BlockchainApi {
+    get_block(block: BlockRef) -> GetBlockResponse => on_get_block;
+    get_block2time(block: BlockRef) -> GetBlock2TimeResponse => on_get_block2time;
+    get_fees_recommended() -> GetFeesRecommendedResponse => on_get_fees_recommended;
+    get_fees_mempool_blocks() -> Vec<GetFeesMempoolBlocksResponse> => on_get_fees_mempool_blocks;
+    get_address_details(params: GetAddressParams) -> GetAddressDetailsResponse
+      => on_get_address_details;
+    get_address_transactions(params: GetAddressParams) -> Vec<GetAddressTransactionsResponse>
+      => on_get_address_transactions;
+    get_address_utxos(params: GetAddressParams) -> Vec<GetAddressUTXOResponse>
+      => on_get_address_utxos;
+    get_hashrate() -> GetHashrateResponse => on_get_hashrate;
+    get_health() -> GetHealthResponse => on_get_health;
+    get_mempool() -> GetMempoolResponse => on_get_mempool;
+    get_transaction(params: GetTransactionParams) -> GetTransactionResponse
+      => on_get_transaction;
+    post_transaction(params: PostTransactionParams) -> PostTransactionResponse
+      => on_post_transaction;
+  }
+}
+
+fake! {
+  /// A programmable [`LightningApi`].
+  FakeLightning: LightningApi {
+    get_node_details(params: GetNodeDetailsParams) -> NodeData => on_get_node_details;
+    get_statistics() -> Statistics => on_get_statistics;
+    get_top_nodes() -> TopData => on_get_top_nodes;
+    get_health() -> GetHealthResponse => on_get_health;
+  }
+}
+
+fake! {
+  /// A programmable [`PricesApi`].
+  FakePrices: PricesApi {
+    convert_currency(params: ConvertCurrencyParams) -> ConvertCurrencyReturn
+      => on_convert_currency;
+    get_ticker(params: GetTickerParams) -> GetTickerReturn => on_get_ticker;
+    get_tickers(params: GetTickerParams) -> GetTickersReturn => on_get_tickers;
+    get_health() -> GetHealthResponse => on_get_health;
+  }
+}
@@ -1,12 +1,68 @@
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
+mod api;
+mod auth;
 mod blockchain;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod builder;
+mod cache;
+#[cfg(feature = "test-utils")]
+mod cassette;
+mod circuit_breaker;
+mod config;
+mod error;
+mod failover;
+#[cfg(feature = "test-utils")]
+pub mod fake;
 mod lightning;
+mod metrics;
+mod middleware;
+mod options;
+mod partial;
 mod prices;
+mod rate_limit;
+mod response;
+mod retry;
+mod single_flight;
+mod telemetry;
+mod transport;
 
-pub use blockchain::types::*;
-pub use lightning::types::*;
-pub use prices::types::*;
+pub use api::{BlockchainApi, LightningApi, PricesApi};
+pub use auth::{Auth, Token};
+pub use builder::{BuilderError, MurrayBuilder};
+pub use cache::{Cache, CacheConfig, CacheStats, CacheTtl};
+#[cfg(feature = "test-utils")]
+pub use cassette::{Cassette, CassetteMode};
+pub use circuit_breaker::{CircuitBreakerConfig, CircuitState};
+pub use config::ConfigError;
+pub use error::{ErrorKind, MurrayError, Result};
+pub use failover::{FailoverPolicy, FailoverStrategy};
+pub use metrics::{LatencyHistogram, MetricsSnapshot, RequestMetrics};
+pub use middleware::{BoxError, HttpService};
+pub use options::RequestOptions;
+pub use partial::{ItemError, Partial};
+pub use rate_limit::{RateLimit, RateLimitMode};
+pub use reqwest;
+pub use response::{CacheStatus, Response, WithMeta};
+pub use retry::RetryPolicy;
+pub use tokio_util::sync::CancellationToken;
+pub use tower;
+
+#[cfg(feature = "disk-cache")]
+pub use blockchain::disk_cache::DiskCacheConfig;
+pub use blockchain::{types::*, Blockchain};
+pub use lightning::{types::*, Lightning};
+pub use prices::{types::*, Prices};
+
+/// The Murray services a call can be made to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum Service {
+  Blockchain,
+  Lightning,
+  Prices,
+}
 
 #[derive(Deserialize, Serialize, Debug)]
 pub struct GetHealthResponse {
@@ -18,6 +74,12 @@ struct GetHealthResponseJsonData {
   pub data: GetHealthResponse,
 }
 
+/// The `data` envelope of any response of the services.
+#[derive(Deserialize)]
+struct JsonData<T> {
+  data: T,
+}
+
 const BASE_BLOCKCHAIN_URL: &str = "http://blockchain.murrayrothbot.com";
 const BASE_PRICES_URL: &str = "http://prices.murrayrothbot.com";
 const BASE_LIGHTNING_URL: &str = "http://lightning.murrayrothbot.com";
@@ -25,25 +87,42 @@ const BASE_LIGHTNING_URL: &str = "http://lightning.murrayrothbot.com";
 /// The [`Murray`] struct is the entrypoint
 /// in order to make calls regarding
 /// prices, blockchain and lightning information.
-/// 
+///
 /// You can call it using either `default` method,
 /// in which all endpoints for those services will
 /// use a default URL, or with `new`, where you can
 /// define what are your endpoints for them.
-/// 
+/// For timeouts, proxies and other HTTP settings,
+/// use [`Murray::builder`].
+///
+/// All calls are `async` and must be `.await`ed from
+/// within a Tokio runtime. The three services share a
+/// single connection pool, and cloning a [`Murray`] is
+/// cheap, so it can be freely handed to other tasks.
+///
+#[derive(Clone)]
 pub struct Murray {
   pub blockchain: blockchain::Blockchain,
   pub prices: prices::Prices,
   pub lightning: lightning::Lightning,
+  cache: Option<Cache>,
+  metrics: metrics::Metrics,
 }
 
 /// Holds the values for blockchain, prices and
 /// lightning endpoints when creating a new instance
 /// of [`Murray`].
+///
+/// The `*_fallback_endpoints` are tried, in order, when
+/// the main endpoint of a service is down. See
+/// [`FailoverPolicy`] for how calls are spread over them.
 pub struct BaseEndpointsParams {
   pub blockchain_endpoint: Option<String>,
   pub prices_endpoint: Option<String>,
   pub lightning_endpoint: Option<String>,
+  pub blockchain_fallback_endpoints: Vec<String>,
+  pub prices_fallback_endpoints: Vec<String>,
+  pub lightning_fallback_endpoints: Vec<String>,
 }
 
 impl Default for BaseEndpointsParams {
@@ -52,52 +131,172 @@ impl Default for BaseEndpointsParams {
       blockchain_endpoint: Some(BASE_BLOCKCHAIN_URL.to_string()),
       prices_endpoint: Some(BASE_PRICES_URL.to_string()),
       lightning_endpoint: Some(BASE_LIGHTNING_URL.to_string()),
+      blockchain_fallback_endpoints: vec![],
+      prices_fallback_endpoints: vec![],
+      lightning_fallback_endpoints: vec![],
     }
   }
 }
 
 impl Murray {
-  pub fn new(
-    BaseEndpointsParams {
-      blockchain_endpoint,
-      prices_endpoint,
-      lightning_endpoint,
-    }: BaseEndpointsParams,
-  ) -> Self {
-    let mut blockchain_url = BASE_BLOCKCHAIN_URL.to_string();
-    if let Some(url) = blockchain_endpoint {
-      blockchain_url = url
-    }
+  pub fn new(params: BaseEndpointsParams) -> Self {
+    Self::builder()
+      .endpoints(params)
+      .build()
+      .expect("failed to build the default HTTP client")
+  }
 
-    let mut prices_url = BASE_PRICES_URL.to_string();
-    if let Some(url) = prices_endpoint {
-      prices_url = url
-    }
+  /// Creates a [`Murray`] configured by environment variables, each
+  /// one overriding the default of the matching [`MurrayBuilder`] setting:
+  ///
+  /// - `MURRAY_{BLOCKCHAIN,PRICES,LIGHTNING}_URL`: base URL of the service;
+  /// - `MURRAY_{...}_FALLBACK_URLS`: comma-separated base URLs to fall back to;
+  /// - `MURRAY_{...}_BEARER_TOKEN`, or `MURRAY_{...}_API_KEY` and
+  ///   `MURRAY_{...}_API_KEY_HEADER` (default `x-api-key`): credentials
+  ///   sent to the service, see [`Auth`];
+  /// - `MURRAY_TIMEOUT`, `MURRAY_CONNECT_TIMEOUT`: e.g. `30s` or `500ms`;
+  /// - `MURRAY_USER_AGENT`;
+  /// - `MURRAY_RETRY_MAX_ATTEMPTS`, `MURRAY_RETRY_INITIAL_BACKOFF`,
+  ///   `MURRAY_RETRY_MAX_BACKOFF`: see [`RetryPolicy`].
+  ///
+  /// Invalid values are reported with a [`ConfigError`]
+  /// naming the variable. Use [`MurrayBuilder::from_env`]
+  /// to configure more settings in code.
+  ///
+  pub fn from_env() -> std::result::Result<Self, ConfigError> {
+    Ok(MurrayBuilder::from_env()?.build()?)
+  }
 
-    let mut lightning_url = BASE_LIGHTNING_URL.to_string();
-    if let Some(url) = lightning_endpoint {
-      lightning_url = url
-    }
+  /// Creates a [`Murray`] configured by a TOML, or YAML (`.yaml`
+  /// or `.yml`), file, with the same settings as [`Murray::from_env`]:
+  ///
+  /// ```toml
+  /// timeout = "30s"
+  /// connect_timeout = "5s"
+  /// user_agent = "my-app/1.0"
+  ///
+  /// [retry]
+  /// max_attempts = 5
+  /// initial_backoff = "200ms"
+  /// max_backoff = "10s"
+  ///
+  /// [blockchain]
+  /// url = "https://blockchain.example.com"
+  /// fallback_urls = ["http://blockchain.murrayrothbot.com"]
+  /// auth = { bearer_token = "some-token" }
+  ///
+  /// [prices.auth]
+  /// api_key = "some-key"
+  /// api_key_header = "x-api-key"
+  /// ```
+  ///
+  /// Durations are either strings such as `"500ms"`, or numbers
+  /// of seconds such as `1.5`. Invalid values and unknown keys are
+  /// reported with a [`ConfigError`] naming the key, e.g. `retry.max_backoff`.
+  ///
+  #[cfg(feature = "config-file")]
+  pub fn from_config_file(
+    path: impl AsRef<std::path::Path>,
+  ) -> std::result::Result<Self, ConfigError> {
+    Ok(MurrayBuilder::from_config_file(path)?.build()?)
+  }
 
-    let blockchain = blockchain::Blockchain::new(blockchain_url);
-    let prices = prices::Prices::new(prices_url);
-    let lightning = lightning::Lightning::new(lightning_url);
+  /// Returns a [`MurrayBuilder`] to configure timeouts,
+  /// user agent, proxies and other HTTP settings.
+  ///
+  pub fn builder() -> MurrayBuilder {
+    MurrayBuilder::new()
+  }
 
-    Self {
-      blockchain,
-      prices,
-      lightning,
+  /// The response cache shared by all services, if one was
+  /// enabled with [`MurrayBuilder::cache`].
+  ///
+  pub fn cache(&self) -> Option<&Cache> {
+    self.cache.as_ref()
+  }
+
+  /// Counters and latency histograms of the requests sent so far
+  /// by all services, per endpoint and status. See [`MetricsSnapshot`].
+  ///
+  pub fn metrics(&self) -> MetricsSnapshot {
+    self.metrics.snapshot()
+  }
+
+  /// Calls `GET {path}` on `service`, with the `query` params, and
+  /// decodes the `data` of its response into `T`. Useful for
+  /// endpoints the crate doesn't model yet, or whose response
+  /// changed in a way its types can't decode anymore.
+  ///
+  /// The call goes through the same retries, auth, cache and
+  /// middleware as the other calls of the service, but its
+  /// metrics and errors are labelled with the `*` endpoint.
+  ///
+  /// ```no_run
+  /// # async fn run() -> murray_rs::Result<()> {
+  /// use murray_rs::{Murray, Service};
+  ///
+  /// #[derive(serde::Deserialize)]
+  /// struct Outspend {
+  ///   spent: bool,
+  /// }
+  ///
+  /// let murray = Murray::default();
+  /// let txid = "15e10745f15593a899cef391191bdd3d7c12412cc4696b7bcb669d0feadc8521";
+  /// let outspends: Vec<Outspend> = murray
+  ///   .call(Service::Blockchain, &format!("/tx/{}/outspends", txid), &[])
+  ///   .await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  ///
+  pub async fn call<T: DeserializeOwned>(
+    &self,
+    service: Service,
+    path: &str,
+    query: &[(&str, &str)],
+  ) -> Result<T> {
+    call(self.transport(service), path, query).await
+  }
+
+  /// Same as [`Murray::call`], leaving the `data`
+  /// of the response as an undecoded JSON value.
+  ///
+  pub async fn call_json(
+    &self,
+    service: Service,
+    path: &str,
+    query: &[(&str, &str)],
+  ) -> Result<serde_json::Value> {
+    self.call(service, path, query).await
+  }
+
+  fn transport(&self, service: Service) -> &transport::Transport {
+    match service {
+      Service::Blockchain => self.blockchain.transport(),
+      Service::Lightning => self.lightning.transport(),
+      Service::Prices => self.prices.transport(),
     }
   }
 }
 
+/// Sends a [`Murray::call`] through `transport`.
+pub(crate) async fn call<T: DeserializeOwned>(
+  transport: &transport::Transport,
+  path: &str,
+  query: &[(&str, &str)],
+) -> Result<T> {
+  let request = transport::Request::custom(path, query);
+  let response = transport.send::<JsonData<T>>(request).await?;
+  Ok(response.into_data().data)
+}
+
 impl Default for Murray {
   /// Creates a default instance of Murray
   /// with default endpoints
   /// - http://blockchain.murrayrothbot.com for blockchain calls;
   /// - http://prices.murrayrothbot.com for prices calls;
   /// - http://lightning.murrayrothbot.com for lightning calls.
-  /// 
+  ///
   fn default() -> Self {
     Self::new(BaseEndpointsParams::default())
   }
@@ -1,141 +1,185 @@
 pub mod types;
-use std::result;
 
 use reqwest::Client;
 
-use crate::{GetHealthResponse, GetHealthResponseJsonData};
+use crate::{
+  error::Result,
+  middleware,
+  transport::{Request, Transport},
+  CircuitState, GetHealthResponse, GetHealthResponseJsonData, RequestOptions, Response, Service,
+  WithMeta,
+};
 
 use self::types::{
   node_details::NodeData, statistics::Statistics, top_nodes::TopData, GetNodeDetailsParams,
-  NodeResponseJsonData, StatisticsJsonData, TopJsonData, LightningError,
+  NodeResponseJsonData, StatisticsJsonData, TopJsonData,
 };
 
-type Result<T> = result::Result<T, LightningError>;
-
+/// Async client for the lightning service.
+///
+/// Cloning is cheap: clones share the same underlying
+/// connection pool.
+///
+#[derive(Clone)]
 pub struct Lightning {
-  base_url: String,
-  client: Client,
+  transport: Transport,
 }
 
 impl Lightning {
   pub fn new(url: String) -> Self {
-    let client = reqwest::Client::new();
+    Self::with_client(url, Client::new())
+  }
+
+  /// Creates a new [`Lightning`] reusing an existing [`Client`],
+  /// so its connection pool can be shared with other services.
+  ///
+  pub fn with_client(url: String, client: Client) -> Self {
     Self {
-      base_url: url,
-      client,
+      transport: Transport::new(Service::Lightning, url, middleware::stack(client, &[])),
     }
   }
 
+  pub(crate) fn from_transport(transport: Transport) -> Self {
+    Self { transport }
+  }
+
+  pub(crate) fn transport(&self) -> &Transport {
+    &self.transport
+  }
+
   /// Change the base url on the fly for the [`Lightning`] calls.
-  /// 
+  ///
   pub fn set_base_url(&mut self, base_url: String) {
-    self.base_url = base_url;
+    self.transport.set_base_url(base_url);
   }
 
-  /// Get information regarding a lightning
-  /// node of a specific [`public_key`](self::types::GetNodeDetailsParams).
-  /// 
-  #[tokio::main]
-  pub async fn get_node_details(
-    &self,
-    GetNodeDetailsParams { public_key }: GetNodeDetailsParams,
-  ) -> Result<NodeData> {
-    let url = format!("{}/node/{}", self.base_url, public_key);
-
-    let client = self.client.get(url).header("Accept", "application/json");
+  /// State of the circuit breaker of the [`Lightning`] service, `None`
+  /// unless one was set with [`MurrayBuilder::circuit_breaker`](crate::MurrayBuilder::circuit_breaker).
+  ///
+  pub fn circuit_state(&self) -> Option<CircuitState> {
+    self.transport.circuit_state()
+  }
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(LightningError::BadRequest(e.to_string())),
-    };
+  /// A view of the [`Lightning`] service whose calls return
+  /// the metadata of their HTTP response along with its data.
+  ///
+  pub fn with_meta(&self) -> WithMeta<'_, Self> {
+    WithMeta { inner: self }
+  }
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<NodeResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(LightningError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(LightningError::APIError(e.to_string())),
-    };
+  /// A copy of the [`Lightning`] service whose calls are made with
+  /// `options`, e.g. a timeout or another base URL.
+  ///
+  pub fn with_options(&self, options: RequestOptions) -> Self {
+    Self::from_transport(self.transport.clone().with_options(options))
+  }
 
-    Ok(data)
+  /// Get information regarding a lightning
+  /// node of a specific [`public_key`](self::types::GetNodeDetailsParams).
+  ///
+  pub async fn get_node_details(&self, params: GetNodeDetailsParams) -> Result<NodeData> {
+    self
+      .with_meta()
+      .get_node_details(params)
+      .await
+      .map(Response::into_data)
   }
 
   /// Get network-wide statistics from the lightning network,
   /// such as total number of channels and nodes, total capacity,
   /// average/median fee figures and more.
-  /// 
-  #[tokio::main]
+  ///
   pub async fn get_statistics(&self) -> Result<Statistics> {
-    let url = format!("{}/statistics", self.base_url);
-
-    let client = self.client.get(url).header("Accept", "application/json");
-
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(LightningError::BadRequest(e.to_string())),
-    };
-
-    let data = match server_response {
-      Ok(resp) => match resp.json::<StatisticsJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(LightningError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(LightningError::APIError(e.to_string())),
-    };
-
-    Ok(data)
+    self
+      .with_meta()
+      .get_statistics()
+      .await
+      .map(Response::into_data)
   }
 
   /// Get two lists of the top nodes:
   /// one ordered by liquidity (aggregate channel capacity)
   /// and the other ordered by connectivity (number of open channels).
-  /// 
-  #[tokio::main]
+  ///
   pub async fn get_top_nodes(&self) -> Result<TopData> {
-    let url = format!("{}/top", self.base_url);
-
-    let client = self.client.get(url).header("Accept", "application/json");
-
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(LightningError::BadRequest(e.to_string())),
-    };
-
-    let data = match server_response {
-      Ok(resp) => match resp.json::<TopJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(LightningError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(LightningError::APIError(e.to_string())),
-    };
-
-    Ok(data)
+    self
+      .with_meta()
+      .get_top_nodes()
+      .await
+      .map(Response::into_data)
   }
 
   /// Get simple information regarding the health
   /// of the lightning service.
-  /// 
+  ///
   /// More info at [service-lightning](https://github.com/murray-rothbot/service-lightning).
-  /// 
-  #[tokio::main]
+  ///
   pub async fn get_health(&self) -> Result<GetHealthResponse> {
-    let url = format!("{}/health", self.base_url);
+    self.with_meta().get_health().await.map(Response::into_data)
+  }
+}
+
+impl WithMeta<'_, Lightning> {
+  /// Same as [`Lightning::get_node_details`], with the metadata of the response.
+  ///
+  pub async fn get_node_details(
+    &self,
+    GetNodeDetailsParams { public_key }: GetNodeDetailsParams,
+  ) -> Result<Response<NodeData>> {
+    let request = Request::get("/node/{public_key}").path(format!("/node/{}", public_key));
 
-    let client = self.client.get(url).header("Accept", "application/json");
+    let response = self
+      .inner
+      .transport
+      .send::<NodeResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
+
+    Ok(response)
+  }
+
+  /// Same as [`Lightning::get_statistics`], with the metadata of the response.
+  ///
+  pub async fn get_statistics(&self) -> Result<Response<Statistics>> {
+    let request = Request::get("/statistics");
+
+    let response = self
+      .inner
+      .transport
+      .send::<StatisticsJsonData>(request)
+      .await?
+      .map(|json| json.data);
+
+    Ok(response)
+  }
+
+  /// Same as [`Lightning::get_top_nodes`], with the metadata of the response.
+  ///
+  pub async fn get_top_nodes(&self) -> Result<Response<TopData>> {
+    let request = Request::get("/top");
+
+    let response = self
+      .inner
+      .transport
+      .send::<TopJsonData>(request)
+      .await?
+      .map(|json| json.data);
+
+    Ok(response)
+  }
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(LightningError::BadRequest(e.to_string())),
-    };
+  /// Same as [`Lightning::get_health`], with the metadata of the response.
+  ///
+  pub async fn get_health(&self) -> Result<Response<GetHealthResponse>> {
+    let request = Request::get("/health");
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetHealthResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(LightningError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(LightningError::APIError(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetHealthResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    Ok(data)
+    Ok(response)
   }
 }
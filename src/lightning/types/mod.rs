@@ -6,19 +6,10 @@ pub mod top_nodes;
 use self::{node_details::NodeData, statistics::Statistics, top_nodes::TopData};
 
 /// [`Lightning`] error
-#[derive(thiserror::Error, Debug)]
-pub enum LightningError {
-  #[error("Invalid URL params: `{0}`")]
-  InvalidURLParams(String),
-  #[error("Bad request: `{0}`")]
-  BadRequest(String),
-  #[error("API error: `{0}`")]
-  APIError(String),
-  #[error("JSON parse error: `{0}`")]
-  JSONParseError(String),
-}
+#[deprecated(since = "0.2.0", note = "use `MurrayError` instead")]
+pub type LightningError = crate::MurrayError;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct GetNodeDetailsParams {
   pub public_key: String,
@@ -1,15 +1,18 @@
 use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Feature {
   pub bit: i32,
   pub name: String,
   pub is_required: bool,
   pub is_known: bool,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct NodeCountry {
   pub de: String,
   pub en: String,
@@ -21,20 +24,24 @@ pub struct NodeCountry {
   pub ru: String,
   #[serde(rename = "zh-CN")]
   pub zh_cn: String,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct ChannelNode {
   pub alias: String,
   pub public_key: String,
   pub channels: i32,
   pub capacity: String,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Channel {
   pub status: i32,
-  pub closing_reason: Option<String>,
+  pub closing_reason: Option<i32>,
   pub closing_date: Option<String>,
   pub capacity: u64,
   pub short_id: String,
@@ -42,6 +49,8 @@ pub struct Channel {
   pub id: String,
   pub fee_rate: i32,
   pub node: ChannelNode,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
 fn deserialize_id<'de, D>(deserializer: D) -> Result<String, D::Error>
@@ -55,7 +64,7 @@ where
   Ok(id_value.to_string())
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct NodeData {
   pub public_key: String,
   pub alias: String,
@@ -63,17 +72,18 @@ pub struct NodeData {
   pub updated_at: u64,
   pub color: String,
   pub sockets: String,
-  pub as_number: i32,
+  // The location of nodes only reachable through Tor is unknown
+  pub as_number: Option<i32>,
   pub city_id: Option<i32>,
-  pub country_id: i32,
+  pub country_id: Option<i32>,
   pub subdivision_id: Option<i32>,
-  pub longitude: f64,
-  pub latitude: f64,
-  pub iso_code: String,
-  pub as_organization: String,
-  pub city: Option<String>,
-  pub country: NodeCountry,
-  pub subdivision: Option<String>,
+  pub longitude: Option<f64>,
+  pub latitude: Option<f64>,
+  pub iso_code: Option<String>,
+  pub as_organization: Option<String>,
+  pub city: Option<HashMap<String, String>>,
+  pub country: Option<NodeCountry>,
+  pub subdivision: Option<HashMap<String, String>>,
   pub features: Vec<Feature>,
   #[serde(rename = "featuresBits")]
   pub features_bits: String,
@@ -83,4 +93,6 @@ pub struct NodeData {
   pub closed_channel_count: i32,
   pub custom_records: HashMap<String, serde_json::Value>,
   pub channels: Vec<Channel>,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
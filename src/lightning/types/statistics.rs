@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 pub struct StatisticsData {
   pub id: u64,
   pub added: String, // ISO 8601 date format
@@ -17,10 +18,14 @@ pub struct StatisticsData {
   pub med_fee_rate: u64,
   pub med_base_fee_mtokens: u64,
   pub clearnet_tor_nodes: u64,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 pub struct Statistics {
   pub latest: StatisticsData,
   pub previous: StatisticsData,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
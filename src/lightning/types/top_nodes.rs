@@ -1,9 +1,10 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::HashMap;
 
 use super::node_details::NodeCountry;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 pub struct NodeInfo {
   #[serde(rename = "publicKey")]
   pub public_key: String,
@@ -14,11 +15,15 @@ pub struct NodeInfo {
   pub country: Option<NodeCountry>,
   pub iso_code: Option<String>,
   pub subdivision: Option<serde_json::Value>,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct TopData {
   pub top_by_capacity: Vec<NodeInfo>,
   pub top_by_channels: Vec<NodeInfo>,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
@@ -0,0 +1,215 @@
+use std::{
+  collections::HashMap,
+  fmt::Write,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+use crate::Service;
+
+/// Upper bounds of the latency histogram buckets, in seconds.
+const BUCKETS: [f64; 11] = [
+  0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Service, endpoint and status of the requests of a [`Series`].
+type SeriesKey = (Service, &'static str, String);
+
+/// Request counters and latency histograms, shared by all
+/// services and clones of a [`Murray`](crate::Murray).
+///
+/// Every request sent to a service is recorded, retries and
+/// failovers included, while responses served by the cache
+/// or shared with an identical request in flight are not.
+#[derive(Clone, Default)]
+pub(crate) struct Metrics {
+  series: Arc<Mutex<HashMap<SeriesKey, Series>>>,
+}
+
+#[derive(Default)]
+struct Series {
+  /// Requests that took at most the matching [`BUCKETS`] bound.
+  buckets: [u64; BUCKETS.len()],
+  count: u64,
+  sum: Duration,
+}
+
+impl Metrics {
+  /// Records a request to `endpoint` of `service`,
+  /// ending with `status` after `elapsed`.
+  pub fn record(&self, service: Service, endpoint: &'static str, status: &str, elapsed: Duration) {
+    let mut series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+    let series = series
+      .entry((service, endpoint, status.to_string()))
+      .or_default();
+
+    let seconds = elapsed.as_secs_f64();
+    for (count, bound) in series.buckets.iter_mut().zip(BUCKETS) {
+      if seconds <= bound {
+        *count += 1;
+      }
+    }
+    series.count += 1;
+    series.sum += elapsed;
+  }
+
+  pub fn snapshot(&self) -> MetricsSnapshot {
+    let series = self.series.lock().unwrap_or_else(|e| e.into_inner());
+    let mut requests: Vec<RequestMetrics> = series
+      .iter()
+      .map(|((service, endpoint, status), series)| RequestMetrics {
+        service: *service,
+        endpoint,
+        status: status.clone(),
+        count: series.count,
+        latency: LatencyHistogram {
+          buckets: BUCKETS
+            .iter()
+            .map(|bound| Duration::from_secs_f64(*bound))
+            .zip(series.buckets)
+            .collect(),
+          sum: series.sum,
+        },
+      })
+      .collect();
+    requests.sort_by(|a, b| {
+      (a.service.to_string(), a.endpoint, &a.status).cmp(&(
+        b.service.to_string(),
+        b.endpoint,
+        &b.status,
+      ))
+    });
+
+    MetricsSnapshot { requests }
+  }
+}
+
+/// The requests sent so far, see [`Murray::metrics`](crate::Murray::metrics).
+///
+/// ```no_run
+/// # async fn run() {
+/// use murray_rs::Murray;
+///
+/// let murray = Murray::default();
+/// let _ = murray.prices.get_health().await;
+///
+/// let metrics = murray.metrics();
+/// let failed: u64 = metrics
+///   .requests
+///   .iter()
+///   .filter(|requests| !requests.is_success())
+///   .map(|requests| requests.count)
+///   .sum();
+/// print!("{}", metrics.to_prometheus());
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetricsSnapshot {
+  /// One entry per service, endpoint and status, sorted by them.
+  pub requests: Vec<RequestMetrics>,
+}
+
+/// The requests sent to an endpoint that ended with the same status.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestMetrics {
+  pub service: Service,
+  /// Route template of the endpoint, e.g. `/tx/{txid}`.
+  pub endpoint: &'static str,
+  /// The HTTP status code of the response, e.g. `200`, or, when
+  /// there was none, one of `timeout`, `connection_error`,
+  /// `rate_limited`, `circuit_open` and `cancelled`.
+  pub status: String,
+  pub count: u64,
+  pub latency: LatencyHistogram,
+}
+
+impl RequestMetrics {
+  /// Whether the requests got a `2xx` response.
+  pub fn is_success(&self) -> bool {
+    self.status.starts_with('2')
+  }
+
+  fn labels(&self) -> String {
+    format!(
+      "service=\"{}\",endpoint=\"{}\",status=\"{}\"",
+      self.service,
+      escape(self.endpoint),
+      escape(&self.status)
+    )
+  }
+}
+
+/// How long requests took, until their whole response was received.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LatencyHistogram {
+  /// Upper bound of each bucket, and how many requests took
+  /// at most that long (so counts are cumulative).
+  pub buckets: Vec<(Duration, u64)>,
+  pub sum: Duration,
+}
+
+impl MetricsSnapshot {
+  /// Renders the metrics in the Prometheus text exposition format,
+  /// as the `murray_requests_total` counter and the
+  /// `murray_request_duration_seconds` histogram, both labelled
+  /// with `service`, `endpoint` and `status`.
+  ///
+  pub fn to_prometheus(&self) -> String {
+    let mut text = String::new();
+
+    text.push_str("# HELP murray_requests_total Requests sent to the Murray APIs.\n");
+    text.push_str("# TYPE murray_requests_total counter\n");
+    for requests in &self.requests {
+      let _ = writeln!(
+        text,
+        "murray_requests_total{{{}}} {}",
+        requests.labels(),
+        requests.count
+      );
+    }
+
+    text.push_str(
+      "# HELP murray_request_duration_seconds Latency of the requests sent to the Murray APIs.\n",
+    );
+    text.push_str("# TYPE murray_request_duration_seconds histogram\n");
+    for requests in &self.requests {
+      let labels = requests.labels();
+      for (bound, count) in &requests.latency.buckets {
+        let _ = writeln!(
+          text,
+          "murray_request_duration_seconds_bucket{{{},le=\"{}\"}} {}",
+          labels,
+          bound.as_secs_f64(),
+          count
+        );
+      }
+      let _ = writeln!(
+        text,
+        "murray_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}",
+        labels, requests.count
+      );
+      let _ = writeln!(
+        text,
+        "murray_request_duration_seconds_sum{{{}}} {}",
+        labels,
+        requests.latency.sum.as_secs_f64()
+      );
+      let _ = writeln!(
+        text,
+        "murray_request_duration_seconds_count{{{}}} {}",
+        labels, requests.count
+      );
+    }
+
+    text
+  }
+}
+
+/// Escapes a Prometheus label value.
+fn escape(value: &str) -> String {
+  value
+    .replace('\\', r"\\")
+    .replace('"', "\\\"")
+    .replace('\n', r"\n")
+}
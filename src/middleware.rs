@@ -0,0 +1,73 @@
+//! The [`tower`] stack every HTTP request of a service is sent
+//! through, so that custom middleware can be plugged in with
+//! [`MurrayBuilder::layer`](crate::MurrayBuilder::layer).
+
+use std::{
+  error::Error as StdError,
+  future::Future,
+  pin::Pin,
+  task::{Context, Poll},
+};
+
+use reqwest::{Client, Request, Response};
+use tower::{util::BoxCloneSyncService, Layer, Service, ServiceExt};
+
+/// Error returned by the services of the stack.
+pub type BoxError = Box<dyn StdError + Send + Sync>;
+
+/// The stack of a service, as seen by the layers added with
+/// [`MurrayBuilder::layer`](crate::MurrayBuilder::layer).
+///
+/// It sends one HTTP request, and is called once per attempt:
+/// retries, failovers and `/health` probes all go through it,
+/// while calls served by the cache or rejected by the rate
+/// limiter or circuit breaker do not.
+pub type HttpService = BoxCloneSyncService<Request, Response, BoxError>;
+
+/// Wraps the stack of a service in a layer.
+pub(crate) type BoxedLayer = Box<dyn Fn(HttpService) -> HttpService + Send + Sync>;
+
+pub(crate) fn boxed_layer<L>(layer: L) -> BoxedLayer
+where
+  L: Layer<HttpService> + Send + Sync + 'static,
+  L::Service: Service<Request, Response = Response> + Clone + Send + Sync + 'static,
+  <L::Service as Service<Request>>::Error: Into<BoxError>,
+  <L::Service as Service<Request>>::Future: Send + 'static,
+{
+  Box::new(move |inner| HttpService::new(layer.layer(inner).map_err(Into::into)))
+}
+
+/// The innermost service, sending requests with a [`Client`].
+#[derive(Clone)]
+struct ClientService(Client);
+
+impl Service<Request> for ClientService {
+  type Response = Response;
+  type Error = BoxError;
+  type Future = Pin<Box<dyn Future<Output = Result<Response, BoxError>> + Send>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), BoxError>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, request: Request) -> Self::Future {
+    let client = self.0.clone();
+    Box::pin(async move { Ok(client.execute(request).await?) })
+  }
+}
+
+/// The stack sending requests with `client`, wrapped in `layers`,
+/// the first one being the outermost.
+pub(crate) fn stack(client: Client, layers: &[BoxedLayer]) -> HttpService {
+  wrap(self::client(client), layers)
+}
+
+/// The innermost service of a stack, sending requests with `client`.
+pub(crate) fn client(client: Client) -> HttpService {
+  HttpService::new(ClientService(client))
+}
+
+/// Wraps `http` in `layers`, the first one being the outermost.
+pub(crate) fn wrap(http: HttpService, layers: &[BoxedLayer]) -> HttpService {
+  layers.iter().rev().fold(http, |inner, layer| layer(inner))
+}
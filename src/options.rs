@@ -0,0 +1,122 @@
+use std::time::{Duration, Instant};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::RetryPolicy;
+
+/// Settings of some calls overriding those of their service, given
+/// to e.g. [`Blockchain::with_options`](crate::Blockchain::with_options).
+///
+/// A latency-critical call can be bounded by a [timeout](Self::timeout)
+/// covering all of its retries, or be [cancelled](Self::cancellation_token)
+/// once its result isn't needed anymore, without changing how the
+/// other calls of the service behave.
+///
+/// ```no_run
+/// # async fn run() -> murray_rs::Result<()> {
+/// use std::time::Duration;
+/// use murray_rs::{Murray, RequestOptions, RetryPolicy};
+///
+/// let murray = Murray::default();
+/// let options = RequestOptions::default()
+///   .timeout(Duration::from_millis(800))
+///   .retry_policy(RetryPolicy::none())
+///   .bypass_cache();
+///
+/// let fees = murray
+///   .blockchain
+///   .with_options(options)
+///   .get_fees_recommended()
+///   .await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug, Clone, Default)]
+pub struct RequestOptions {
+  pub(crate) timeout: Option<Duration>,
+  pub(crate) deadline: Option<Instant>,
+  pub(crate) cancellation_token: Option<CancellationToken>,
+  pub(crate) retry_policy: Option<RetryPolicy>,
+  pub(crate) bypass_cache: bool,
+  pub(crate) base_url: Option<String>,
+}
+
+impl RequestOptions {
+  /// Fails each call with [`ErrorKind::DeadlineExceeded`](crate::ErrorKind::DeadlineExceeded)
+  /// when it takes longer than `timeout`, retries and failovers included.
+  ///
+  pub fn timeout(mut self, timeout: Duration) -> Self {
+    self.timeout = Some(timeout);
+    self
+  }
+
+  /// Fails the calls with [`ErrorKind::DeadlineExceeded`](crate::ErrorKind::DeadlineExceeded)
+  /// when they are still running at `deadline`.
+  ///
+  pub fn deadline(mut self, deadline: Instant) -> Self {
+    self.deadline = Some(deadline);
+    self
+  }
+
+  /// Fails the calls with [`ErrorKind::Cancelled`](crate::ErrorKind::Cancelled)
+  /// as soon as `token` is cancelled, dropping their requests.
+  ///
+  pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+    self.cancellation_token = Some(token);
+    self
+  }
+
+  /// Retries the calls as told by `retry_policy`
+  /// instead of the policy of the service.
+  ///
+  pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+    self.retry_policy = Some(retry_policy);
+    self
+  }
+
+  /// Requests fresh responses, which still refresh the cache,
+  /// instead of serving the calls from the [`Cache`](crate::Cache)
+  /// or [disk cache](crate::MurrayBuilder::disk_cache).
+  ///
+  pub fn bypass_cache(mut self) -> Self {
+    self.bypass_cache = true;
+    self
+  }
+
+  /// Sends the calls to `base_url` instead of the base URLs of the
+  /// service, e.g. the node of one tenant. Their responses are
+  /// cached apart from those of the other base URLs.
+  ///
+  /// The calls don't fail over to the fallback endpoints of the
+  /// service, and share a circuit breaker with the other calls to
+  /// `base_url` only, so that its failures don't open the circuit of
+  /// the service. They still count against its
+  /// [`RateLimit`](crate::RateLimit).
+  ///
+  pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+    self.base_url = Some(base_url.into());
+    self
+  }
+
+  /// These options, overridden by those set in `other`.
+  pub(crate) fn merge(self, other: RequestOptions) -> Self {
+    Self {
+      timeout: other.timeout.or(self.timeout),
+      deadline: other.deadline.or(self.deadline),
+      cancellation_token: other.cancellation_token.or(self.cancellation_token),
+      retry_policy: other.retry_policy.or(self.retry_policy),
+      bypass_cache: other.bypass_cache || self.bypass_cache,
+      base_url: other.base_url.or(self.base_url),
+    }
+  }
+
+  /// When a call started at `started` must be over.
+  pub(crate) fn deadline_of(&self, started: Instant) -> Option<Instant> {
+    let timeout = self.timeout.map(|timeout| started + timeout);
+    match (timeout, self.deadline) {
+      (Some(a), Some(b)) => Some(a.min(b)),
+      (a, b) => a.or(b),
+    }
+  }
+}
@@ -0,0 +1,87 @@
+use std::{error::Error as StdError, fmt};
+
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// The items of a list response decoded one by one, so that those
+/// which can't be decoded don't fail the whole call. Returned by
+/// e.g. [`Blockchain::get_address_utxos_lenient`](crate::Blockchain::get_address_utxos_lenient).
+///
+/// ```no_run
+/// # async fn run() -> murray_rs::Result<()> {
+/// use murray_rs::{GetAddressParams, Murray};
+///
+/// let murray = Murray::default();
+/// let utxos = murray
+///   .blockchain
+///   .get_address_utxos_lenient(GetAddressParams {
+///     address: "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh".to_string(),
+///   })
+///   .await?;
+///
+/// for error in &utxos.errors {
+///   eprintln!("{}, raw item: {}", error, error.raw);
+/// }
+/// let balance: u64 = utxos.items.iter().map(|utxo| utxo.value).sum();
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Debug)]
+pub struct Partial<T> {
+  /// The items which were decoded, in the order of the response.
+  pub items: Vec<T>,
+  /// The items which could not be decoded.
+  pub errors: Vec<ItemError>,
+}
+
+impl<T: DeserializeOwned> Partial<T> {
+  pub(crate) fn decode(raw: Vec<Value>) -> Self {
+    let mut partial = Self {
+      items: Vec::with_capacity(raw.len()),
+      errors: vec![],
+    };
+
+    for (index, raw) in raw.into_iter().enumerate() {
+      match T::deserialize(&raw) {
+        Ok(item) => partial.items.push(item),
+        Err(error) => partial.errors.push(ItemError { index, error, raw }),
+      }
+    }
+
+    partial
+  }
+}
+
+impl<T> Partial<T> {
+  /// `true` if every item was decoded.
+  pub fn is_complete(&self) -> bool {
+    self.errors.is_empty()
+  }
+}
+
+/// An item of a list response which could not be decoded.
+#[derive(Debug)]
+pub struct ItemError {
+  /// Position of the item in the response.
+  pub index: usize,
+  pub error: serde_json::Error,
+  /// The item, as it was received.
+  pub raw: Value,
+}
+
+impl fmt::Display for ItemError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "item {} could not be decoded: {}",
+      self.index, self.error
+    )
+  }
+}
+
+impl StdError for ItemError {
+  fn source(&self) -> Option<&(dyn StdError + 'static)> {
+    Some(&self.error)
+  }
+}
@@ -1,164 +1,199 @@
 pub mod types;
-use std::result;
 
 use reqwest::Client;
 
-use crate::{GetHealthResponse, GetHealthResponseJsonData};
+use crate::{
+  error::Result,
+  middleware,
+  transport::{Request, Transport},
+  CircuitState, GetHealthResponse, GetHealthResponseJsonData, RequestOptions, Response, Service,
+  WithMeta,
+};
 
 use self::types::{
   ConvertCurrencyJsonData, ConvertCurrencyParams, ConvertCurrencyReturn, GetTickerJsonData,
-  GetTickerParams, GetTickerReturn, GetTickersJsonData, GetTickersReturn, PriceError
+  GetTickerParams, GetTickerReturn, GetTickersJsonData, GetTickersReturn,
 };
 
-type Result<T> = result::Result<T, PriceError>;
-
+/// Async client for the prices service.
+///
+/// Cloning is cheap: clones share the same underlying
+/// connection pool.
+///
+#[derive(Clone)]
 pub struct Prices {
-  base_url: String,
-  client: Client,
+  transport: Transport,
 }
 
 impl Prices {
   pub fn new(url: String) -> Self {
-    let client = reqwest::Client::new();
+    Self::with_client(url, Client::new())
+  }
+
+  /// Creates a new [`Prices`] reusing an existing [`Client`],
+  /// so its connection pool can be shared with other services.
+  ///
+  pub fn with_client(url: String, client: Client) -> Self {
     Self {
-      base_url: url,
-      client,
+      transport: Transport::new(Service::Prices, url, middleware::stack(client, &[])),
     }
   }
 
+  pub(crate) fn from_transport(transport: Transport) -> Self {
+    Self { transport }
+  }
+
+  pub(crate) fn transport(&self) -> &Transport {
+    &self.transport
+  }
+
   /// Change the base url on the fly for the [`Prices`] calls.
-  /// 
+  ///
   pub fn set_base_url(&mut self, base_url: String) {
-    self.base_url = base_url;
+    self.transport.set_base_url(base_url);
+  }
+
+  /// State of the circuit breaker of the [`Prices`] service, `None`
+  /// unless one was set with [`MurrayBuilder::circuit_breaker`](crate::MurrayBuilder::circuit_breaker).
+  ///
+  pub fn circuit_state(&self) -> Option<CircuitState> {
+    self.transport.circuit_state()
+  }
+
+  /// A view of the [`Prices`] service whose calls return
+  /// the metadata of their HTTP response along with its data.
+  ///
+  pub fn with_meta(&self) -> WithMeta<'_, Self> {
+    WithMeta { inner: self }
+  }
+
+  /// A copy of the [`Prices`] service whose calls are made with
+  /// `options`, e.g. a timeout or another base URL.
+  ///
+  pub fn with_options(&self, options: RequestOptions) -> Self {
+    Self::from_transport(self.transport.clone().with_options(options))
   }
 
   /// Converts a value - in a [`Currency`](self::types::Currency) -
   /// into other currencies defined by [`ConvertCurrencyReturn`](self::types::ConvertCurrencyReturn).
   ///
-  #[tokio::main]
   pub async fn convert_currency(
     &self,
-    ConvertCurrencyParams { currency, value }: ConvertCurrencyParams,
+    params: ConvertCurrencyParams,
   ) -> Result<ConvertCurrencyReturn> {
-    let url = format!("{}/convert", self.base_url);
-    let params = [
+    self
+      .with_meta()
+      .convert_currency(params)
+      .await
+      .map(Response::into_data)
+  }
+
+  /// Get a ticker information of a trading pair ([`Symbol`](self::types::Symbol))
+  /// from a specific exchange.
+  pub async fn get_ticker(&self, params: GetTickerParams) -> Result<GetTickerReturn> {
+    self
+      .with_meta()
+      .get_ticker(params)
+      .await
+      .map(Response::into_data)
+  }
+
+  /// Get tickers information of a trading pair ([`Symbol`](self::types::Symbol))
+  /// from different exchanges.
+  pub async fn get_tickers(&self, params: GetTickerParams) -> Result<GetTickersReturn> {
+    self
+      .with_meta()
+      .get_tickers(params)
+      .await
+      .map(Response::into_data)
+  }
+
+  /// Get simple information regarding the health
+  /// of the prices service.
+  ///
+  /// More info at [service-prices](https://github.com/murray-rothbot/service-prices).
+  ///
+  pub async fn get_health(&self) -> Result<GetHealthResponse> {
+    self.with_meta().get_health().await.map(Response::into_data)
+  }
+}
+
+impl WithMeta<'_, Prices> {
+  /// Same as [`Prices::convert_currency`], with the metadata of the response.
+  ///
+  pub async fn convert_currency(
+    &self,
+    ConvertCurrencyParams { currency, value }: ConvertCurrencyParams,
+  ) -> Result<Response<ConvertCurrencyReturn>> {
+    let params = vec![
       ("currency", currency.to_string()),
       ("value", value.to_string()),
     ];
 
-    let url = match reqwest::Url::parse_with_params(&url, &params) {
-      Ok(url) => url,
-      Err(e) => return Err(PriceError::InvalidURLParams(e.to_string())),
-    };
+    let request = Request::get("/convert").query(params);
 
-    let client = self.client.get(url).header("Accept", "application/json");
+    let response = self
+      .inner
+      .transport
+      .send::<ConvertCurrencyJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(PriceError::BadRequest(e.to_string())),
-    };
-
-    let data = match server_response {
-      Ok(resp) => match resp.json::<ConvertCurrencyJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(PriceError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(PriceError::APIError(e.to_string())),
-    };
-
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get a ticker information of a trading pair ([`Symbol`](self::types::Symbol))
-  /// from a specific exchange.
-  #[tokio::main]
+  /// Same as [`Prices::get_ticker`], with the metadata of the response.
+  ///
   pub async fn get_ticker(
     &self,
     GetTickerParams { symbol }: GetTickerParams,
-  ) -> Result<GetTickerReturn> {
-    let url = format!("{}/ticker", self.base_url);
-    let params = [("symbol", symbol.to_string())];
-
-    let url = match reqwest::Url::parse_with_params(&url, &params) {
-      Ok(url) => url,
-      Err(e) => return Err(PriceError::InvalidURLParams(e.to_string())),
-    };
+  ) -> Result<Response<GetTickerReturn>> {
+    let params = vec![("symbol", symbol.to_string())];
 
-    let client = self.client.get(url).header("Accept", "application/json");
+    let request = Request::get("/ticker").query(params);
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(PriceError::BadRequest(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetTickerJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetTickerJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(PriceError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(PriceError::APIError(e.to_string())),
-    };
-
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get tickers information of a trading pair ([`Symbol`](self::types::Symbol))
-  /// from different exchanges.
-  #[tokio::main]
+  /// Same as [`Prices::get_tickers`], with the metadata of the response.
+  ///
   pub async fn get_tickers(
     &self,
     GetTickerParams { symbol }: GetTickerParams,
-  ) -> Result<GetTickersReturn> {
-    let url = format!("{}/tickers", self.base_url);
-    let params = [("symbol", symbol.to_string())];
-
-    let url = match reqwest::Url::parse_with_params(&url, &params) {
-      Ok(url) => url,
-      Err(e) => return Err(PriceError::InvalidURLParams(e.to_string())),
-    };
-
-    let client = self.client.get(url).header("Accept", "application/json");
+  ) -> Result<Response<GetTickersReturn>> {
+    let params = vec![("symbol", symbol.to_string())];
 
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(PriceError::BadRequest(e.to_string())),
-    };
+    let request = Request::get("/tickers").query(params);
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetTickersJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(PriceError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(PriceError::APIError(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetTickersJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    Ok(data)
+    Ok(response)
   }
 
-  /// Get simple information regarding the health
-  /// of the prices service.
-  /// 
-  /// More info at [service-prices](https://github.com/murray-rothbot/service-prices).
-  /// 
-  #[tokio::main]
-  pub async fn get_health(&self) -> Result<GetHealthResponse> {
-    let url = format!("{}/health", self.base_url);
-
-    let client = self.client.get(url).header("Accept", "application/json");
-
-    let server_response = match client.send().await {
-      Ok(resp) => resp.error_for_status(),
-      Err(e) => return Err(PriceError::BadRequest(e.to_string())),
-    };
+  /// Same as [`Prices::get_health`], with the metadata of the response.
+  ///
+  pub async fn get_health(&self) -> Result<Response<GetHealthResponse>> {
+    let request = Request::get("/health");
 
-    let data = match server_response {
-      Ok(resp) => match resp.json::<GetHealthResponseJsonData>().await {
-        Ok(r) => r.data,
-        Err(e) => return Err(PriceError::JSONParseError(e.to_string())),
-      },
-      Err(e) => return Err(PriceError::APIError(e.to_string())),
-    };
+    let response = self
+      .inner
+      .transport
+      .send::<GetHealthResponseJsonData>(request)
+      .await?
+      .map(|json| json.data);
 
-    Ok(data)
+    Ok(response)
   }
 }
@@ -1,17 +1,9 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 
 /// [`Price`] error
-#[derive(thiserror::Error, Debug)]
-pub enum PriceError {
-  #[error("Invalid URL params: `{0}`")]
-  InvalidURLParams(String),
-  #[error("Bad request: `{0}`")]
-  BadRequest(String),
-  #[error("API error: `{0}`")]
-  APIError(String),
-  #[error("JSON parse error: `{0}`")]
-  JSONParseError(String),
-}
+#[deprecated(since = "0.2.0", note = "use `MurrayError` instead")]
+pub type PriceError = crate::MurrayError;
 
 #[derive(Debug, PartialEq, strum::Display)]
 pub enum Currency {
@@ -26,12 +18,14 @@ pub struct ConvertCurrencyParams {
   pub value: i64,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct ConvertCurrencyReturn {
   pub btc: String,
   pub usd: String,
   pub brl: String,
   pub sat: String,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
 #[derive(Debug, PartialEq, strum::Display)]
@@ -45,25 +39,31 @@ pub struct GetTickerParams {
   pub symbol: Symbol,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetTickerReturn {
   pub price: String,
-  pub change24h: String,
+  pub change24h: Option<String>,
   pub source: String,
   pub symbol: String,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct Tickers {
   pub price: String,
   pub change24h: Option<String>,
   pub source: String,
   pub symbol: String,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
-#[derive(Deserialize, Serialize, Debug)]
+#[derive(Deserialize, Serialize, Debug, Default)]
 pub struct GetTickersReturn {
   pub tickers: Vec<Tickers>,
+  #[serde(flatten)]
+  pub extra: Map<String, Value>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -0,0 +1,161 @@
+use std::{
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+/// What to do with a call exceeding its [`RateLimit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateLimitMode {
+  /// Wait until a permit is available, then send the call.
+  Wait,
+  /// Fail immediately with an [`ErrorKind::RateLimited`](crate::ErrorKind::RateLimited)
+  /// error, whose [`retry_after`](crate::MurrayError::retry_after) tells
+  /// when a permit will be available.
+  FailFast,
+}
+
+/// Client-side limit of how many calls are sent to a service,
+/// implemented as a token bucket.
+///
+/// `requests` permits are handed out every `per` period, and up to
+/// `burst` of them (by default, `requests`) can be saved up and
+/// used at once. Every attempt of a call takes one permit, so
+/// retries count against the limit too.
+///
+/// ```
+/// use murray_rs::{Murray, RateLimit, Service};
+///
+/// let murray = Murray::builder()
+///   .rate_limit(Service::Blockchain, RateLimit::per_second(10))
+///   .rate_limit(Service::Lightning, RateLimit::per_minute(60).fail_fast())
+///   .build()
+///   .unwrap();
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct RateLimit {
+  requests: u32,
+  per: Duration,
+  burst: u32,
+  mode: RateLimitMode,
+}
+
+impl RateLimit {
+  pub fn new(requests: u32, per: Duration) -> Self {
+    let requests = requests.max(1);
+    Self {
+      requests,
+      per,
+      burst: requests,
+      mode: RateLimitMode::Wait,
+    }
+  }
+
+  pub fn per_second(requests: u32) -> Self {
+    Self::new(requests, Duration::from_secs(1))
+  }
+
+  pub fn per_minute(requests: u32) -> Self {
+    Self::new(requests, Duration::from_secs(60))
+  }
+
+  /// How many permits can be used at once after a quiet period.
+  ///
+  pub fn burst(mut self, burst: u32) -> Self {
+    self.burst = burst.max(1);
+    self
+  }
+
+  pub fn mode(mut self, mode: RateLimitMode) -> Self {
+    self.mode = mode;
+    self
+  }
+
+  /// Shortcut for [`mode(RateLimitMode::FailFast)`](Self::mode).
+  ///
+  pub fn fail_fast(self) -> Self {
+    self.mode(RateLimitMode::FailFast)
+  }
+
+  fn permits_per_second(&self) -> f64 {
+    self.requests as f64 / self.per.as_secs_f64().max(f64::EPSILON)
+  }
+}
+
+/// The token bucket enforcing a [`RateLimit`],
+/// shared by all clones of a service.
+pub(crate) struct RateLimiter {
+  limit: RateLimit,
+  bucket: Mutex<Bucket>,
+}
+
+struct Bucket {
+  /// Negative when permits have been reserved by waiting calls.
+  tokens: f64,
+  refilled_at: Instant,
+}
+
+impl RateLimiter {
+  pub fn new(limit: RateLimit) -> Self {
+    let bucket = Bucket {
+      tokens: limit.burst as f64,
+      refilled_at: Instant::now(),
+    };
+
+    Self {
+      limit,
+      bucket: Mutex::new(bucket),
+    }
+  }
+
+  /// Takes a permit, waiting for it in [`RateLimitMode::Wait`].
+  /// In [`RateLimitMode::FailFast`], returns how long until one
+  /// is available instead of taking it.
+  pub async fn acquire(&self) -> Result<(), Duration> {
+    let wait = {
+      let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+      let rate = self.limit.permits_per_second();
+
+      let now = Instant::now();
+      let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+      bucket.tokens = (bucket.tokens + elapsed * rate).min(self.limit.burst as f64);
+      bucket.refilled_at = now;
+
+      if bucket.tokens >= 1.0 {
+        bucket.tokens -= 1.0;
+        return Ok(());
+      }
+
+      let wait = Duration::from_secs_f64((1.0 - bucket.tokens) / rate);
+      if self.limit.mode == RateLimitMode::FailFast {
+        return Err(wait);
+      }
+
+      // reserve the permit now, so that waiting calls are served in order
+      bucket.tokens -= 1.0;
+      wait
+    };
+
+    let reservation = Reservation { limiter: self };
+    tokio::time::sleep(wait).await;
+    std::mem::forget(reservation);
+    Ok(())
+  }
+}
+
+/// A permit reserved by a waiting call, handed back to the bucket
+/// when the call is dropped (e.g. cancelled) before its turn.
+struct Reservation<'a> {
+  limiter: &'a RateLimiter,
+}
+
+impl Drop for Reservation<'_> {
+  fn drop(&mut self) {
+    let mut bucket = self
+      .limiter
+      .bucket
+      .lock()
+      .unwrap_or_else(|e| e.into_inner());
+    bucket.tokens = (bucket.tokens + 1.0).min(self.limiter.limit.burst as f64);
+  }
+}
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use bytes::Bytes;
+use reqwest::{header::HeaderMap, StatusCode, Url};
+
+/// Whether a [`Response`] was served by the [`Cache`](crate::Cache).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheStatus {
+  /// Served from the cache, without any request.
+  Hit,
+  /// Looked up in the cache but not found, so requested.
+  Miss,
+  /// Requested without looking the cache up, as there is none,
+  /// the response of the endpoint is never cached, or the call
+  /// [bypassed](crate::RequestOptions::bypass_cache) it.
+  Bypass,
+}
+
+/// Decoded data of a call, along with the HTTP response it was
+/// decoded from. Returned by the calls of [`WithMeta`].
+///
+/// Responses served from a cache only keep their body, so their
+/// status is `200 OK` and their headers are empty.
+///
+#[derive(Debug, Clone)]
+pub struct Response<T> {
+  data: T,
+  status: StatusCode,
+  headers: HeaderMap,
+  elapsed: Duration,
+  url: Url,
+  cache_status: CacheStatus,
+  body: Bytes,
+}
+
+/// A response of a service, before its body is decoded.
+#[derive(Clone)]
+pub(crate) struct RawResponse {
+  pub status: StatusCode,
+  pub headers: HeaderMap,
+  /// URL which served the response.
+  pub url: Url,
+  pub body: Bytes,
+}
+
+impl<T> Response<T> {
+  pub(crate) fn new(
+    data: T,
+    raw: RawResponse,
+    elapsed: Duration,
+    cache_status: CacheStatus,
+  ) -> Self {
+    Self {
+      data,
+      status: raw.status,
+      headers: raw.headers,
+      elapsed,
+      url: raw.url,
+      cache_status,
+      body: raw.body,
+    }
+  }
+
+  /// The decoded data.
+  pub fn data(&self) -> &T {
+    &self.data
+  }
+
+  pub fn into_data(self) -> T {
+    self.data
+  }
+
+  /// HTTP status of the response.
+  pub fn status(&self) -> StatusCode {
+    self.status
+  }
+
+  /// HTTP headers of the response.
+  pub fn headers(&self) -> &HeaderMap {
+    &self.headers
+  }
+
+  /// How long the call took, retries and failovers included.
+  pub fn elapsed(&self) -> Duration {
+    self.elapsed
+  }
+
+  /// URL the response came from, whose base URL may be
+  /// a fallback one. For cached responses, the URL they
+  /// would have been requested from.
+  pub fn url(&self) -> &Url {
+    &self.url
+  }
+
+  pub fn cache_status(&self) -> CacheStatus {
+    self.cache_status
+  }
+
+  /// Undecoded body of the response.
+  pub fn body(&self) -> &Bytes {
+    &self.body
+  }
+
+  /// Replaces the data with `f(data)`, keeping the metadata.
+  pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Response<U> {
+    Response {
+      data: f(self.data),
+      status: self.status,
+      headers: self.headers,
+      elapsed: self.elapsed,
+      url: self.url,
+      cache_status: self.cache_status,
+      body: self.body,
+    }
+  }
+}
+
+/// A service whose calls return a [`Response`], with the
+/// metadata of the HTTP response along with its data.
+/// Obtained with e.g. [`Blockchain::with_meta`](crate::Blockchain::with_meta).
+///
+/// ```no_run
+/// # async fn run() -> murray_rs::Result<()> {
+/// use murray_rs::{BlockRef, CacheStatus, Murray};
+///
+/// let murray = Murray::default();
+/// let response = murray
+///   .blockchain
+///   .with_meta()
+///   .get_block(BlockRef::Height(800_000))
+///   .await?;
+///
+/// println!(
+///   "{} from {} in {:?}, cached: {}",
+///   response.status(),
+///   response.url(),
+///   response.elapsed(),
+///   response.cache_status() == CacheStatus::Hit,
+/// );
+/// let block = response.into_data();
+/// # Ok(())
+/// # }
+/// ```
+///
+#[derive(Clone, Copy)]
+pub struct WithMeta<'a, S> {
+  pub(crate) inner: &'a S,
+}
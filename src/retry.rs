@@ -0,0 +1,221 @@
+use std::time::{Duration, SystemTime};
+
+use reqwest::{header::HeaderMap, Method, StatusCode};
+
+use crate::{error::MurrayError, ErrorKind};
+
+/// Decides whether, and after how long, a failed call is sent again.
+///
+/// The default policy makes up to 3 attempts of idempotent (`GET`/`HEAD`)
+/// calls failing with a connection error, a timeout, or one of the
+/// `408`, `429`, `500`, `502`, `503` and `504` statuses, waiting
+/// 200ms, then 400ms (with jitter) in between, or whatever the
+/// service asks for in its `Retry-After` header.
+///
+/// `POST` calls such as [`post_transaction`](crate::Blockchain::post_transaction)
+/// are never retried unless opted in with [`RetryPolicy::retry_post`].
+///
+/// ```
+/// use std::time::Duration;
+/// use murray_rs::RetryPolicy;
+///
+/// let policy = RetryPolicy::default()
+///   .max_attempts(5)
+///   .initial_backoff(Duration::from_millis(500));
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+  max_attempts: u32,
+  initial_backoff: Duration,
+  max_backoff: Duration,
+  multiplier: f64,
+  jitter: bool,
+  statuses: Vec<StatusCode>,
+  methods: Vec<Method>,
+  retry_connection_errors: bool,
+  respect_retry_after: bool,
+  max_retry_after: Duration,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self {
+      max_attempts: 3,
+      initial_backoff: Duration::from_millis(200),
+      max_backoff: Duration::from_secs(10),
+      multiplier: 2.0,
+      jitter: true,
+      statuses: vec![
+        StatusCode::REQUEST_TIMEOUT,
+        StatusCode::TOO_MANY_REQUESTS,
+        StatusCode::INTERNAL_SERVER_ERROR,
+        StatusCode::BAD_GATEWAY,
+        StatusCode::SERVICE_UNAVAILABLE,
+        StatusCode::GATEWAY_TIMEOUT,
+      ],
+      methods: vec![Method::GET, Method::HEAD],
+      retry_connection_errors: true,
+      respect_retry_after: true,
+      max_retry_after: Duration::from_secs(60),
+    }
+  }
+}
+
+impl RetryPolicy {
+  /// A policy that never retries.
+  ///
+  pub fn none() -> Self {
+    Self::default().max_attempts(1)
+  }
+
+  /// Total number of attempts, including the first one.
+  /// `1` disables retries.
+  ///
+  pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+    self.max_attempts = max_attempts.max(1);
+    self
+  }
+
+  /// Wait before the first retry. Each following retry
+  /// waits `multiplier` times longer, up to `max_backoff`.
+  ///
+  pub fn initial_backoff(mut self, backoff: Duration) -> Self {
+    self.initial_backoff = backoff;
+    self
+  }
+
+  pub fn max_backoff(mut self, backoff: Duration) -> Self {
+    self.max_backoff = backoff;
+    self
+  }
+
+  pub fn multiplier(mut self, multiplier: f64) -> Self {
+    self.multiplier = multiplier.max(1.0);
+    self
+  }
+
+  /// Randomizes each wait between half and all of its
+  /// computed value, so that clients failing together
+  /// don't retry together.
+  ///
+  pub fn jitter(mut self, jitter: bool) -> Self {
+    self.jitter = jitter;
+    self
+  }
+
+  /// HTTP statuses worth retrying.
+  ///
+  pub fn statuses(mut self, statuses: impl IntoIterator<Item = StatusCode>) -> Self {
+    self.statuses = statuses.into_iter().collect();
+    self
+  }
+
+  /// HTTP methods worth retrying.
+  ///
+  pub fn methods(mut self, methods: impl IntoIterator<Item = Method>) -> Self {
+    self.methods = methods.into_iter().collect();
+    self
+  }
+
+  /// Also retry `POST` calls, i.e.
+  /// [`post_transaction`](crate::Blockchain::post_transaction).
+  ///
+  /// Broadcasting the same transaction twice is harmless, but the
+  /// second attempt may fail because the first one did go through.
+  ///
+  pub fn retry_post(mut self, enabled: bool) -> Self {
+    self.methods.retain(|method| method != Method::POST);
+    if enabled {
+      self.methods.push(Method::POST);
+    }
+    self
+  }
+
+  /// Whether to retry when no response was received
+  /// (connection refused, reset, timeout...).
+  ///
+  pub fn retry_connection_errors(mut self, enabled: bool) -> Self {
+    self.retry_connection_errors = enabled;
+    self
+  }
+
+  /// Whether to wait for as long as the service asks in its
+  /// `Retry-After` header instead of the computed backoff.
+  /// A `Retry-After` longer than `max_retry_after` stops retrying.
+  ///
+  pub fn respect_retry_after(mut self, enabled: bool) -> Self {
+    self.respect_retry_after = enabled;
+    self
+  }
+
+  pub fn max_retry_after(mut self, max: Duration) -> Self {
+    self.max_retry_after = max;
+    self
+  }
+
+  /// Whether calls with `method` may be sent more than once.
+  pub(crate) fn retries(&self, method: &Method) -> bool {
+    self.methods.contains(method)
+  }
+
+  /// How long to wait before sending `method` again after
+  /// its `attempt`-th try (starting at 1) failed with `error`,
+  /// or `None` if it should not be retried.
+  pub(crate) fn backoff(
+    &self,
+    method: &Method,
+    attempt: u32,
+    error: &MurrayError,
+  ) -> Option<Duration> {
+    if attempt >= self.max_attempts || !self.retries(method) {
+      return None;
+    }
+
+    let retryable = match error.kind() {
+      ErrorKind::Request => self.retry_connection_errors && error.is_retryable(),
+      ErrorKind::API => error
+        .status()
+        .map(|status| self.statuses.contains(&status))
+        .unwrap_or(false),
+      _ => false,
+    };
+    if !retryable {
+      return None;
+    }
+
+    if self.respect_retry_after {
+      if let Some(retry_after) = error.retry_after() {
+        return (retry_after <= self.max_retry_after).then_some(retry_after);
+      }
+    }
+
+    let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+    let backoff = (self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent))
+      .min(self.max_backoff.as_secs_f64());
+    let backoff = Duration::from_secs_f64(backoff);
+
+    if self.jitter {
+      Some(backoff.mul_f64(0.5 + fastrand::f64() / 2.0))
+    } else {
+      Some(backoff)
+    }
+  }
+}
+
+/// Parses a `Retry-After` header, given either
+/// in seconds or as an HTTP date.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+  let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+
+  if let Ok(seconds) = value.trim().parse::<u64>() {
+    return Some(Duration::from_secs(seconds));
+  }
+
+  let date = httpdate::parse_http_date(value).ok()?;
+  Some(
+    date
+      .duration_since(SystemTime::now())
+      .unwrap_or(Duration::ZERO),
+  )
+}
@@ -0,0 +1,88 @@
+use std::{
+  collections::HashMap,
+  future::Future,
+  sync::{Arc, Mutex},
+};
+
+use tokio::sync::broadcast;
+
+use crate::{error::Result, response::RawResponse};
+
+/// Collapses identical requests sent at the same time into one
+/// call, whose result is handed to every caller waiting for it.
+#[derive(Clone, Default)]
+pub(crate) struct SingleFlight {
+  in_flight: Arc<Mutex<HashMap<String, broadcast::Sender<Result<RawResponse>>>>>,
+}
+
+/// Removes the call of the leading caller from the in-flight ones,
+/// even when it is cancelled before finishing.
+struct Leader<'a> {
+  single_flight: &'a SingleFlight,
+  key: Option<String>,
+}
+
+impl Leader<'_> {
+  fn finish(mut self, result: &Result<RawResponse>) {
+    if let Some(sender) = self
+      .key
+      .take()
+      .and_then(|key| self.single_flight.remove(&key))
+    {
+      // nobody waiting is fine
+      let _ = sender.send(result.clone());
+    }
+  }
+}
+
+impl Drop for Leader<'_> {
+  fn drop(&mut self) {
+    if let Some(key) = self.key.take() {
+      self.single_flight.remove(&key);
+    }
+  }
+}
+
+impl SingleFlight {
+  fn remove(&self, key: &str) -> Option<broadcast::Sender<Result<RawResponse>>> {
+    let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+    in_flight.remove(key)
+  }
+
+  /// Runs `call`, unless a call with the same `key` is already
+  /// in flight, in which case its result is awaited instead.
+  pub async fn run<F, Fut>(&self, key: String, call: F) -> Result<RawResponse>
+  where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<RawResponse>>,
+  {
+    let waiting = {
+      let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+      match in_flight.get(&key) {
+        Some(sender) => Some(sender.subscribe()),
+        None => {
+          let (sender, _) = broadcast::channel(1);
+          in_flight.insert(key.clone(), sender);
+          None
+        }
+      }
+    };
+
+    if let Some(mut receiver) = waiting {
+      return match receiver.recv().await {
+        Ok(result) => result,
+        // the leading caller was cancelled, go on alone
+        Err(_) => call().await,
+      };
+    }
+
+    let leader = Leader {
+      single_flight: self,
+      key: Some(key),
+    };
+    let result = call().await;
+    leader.finish(&result);
+
+    result
+  }
+}
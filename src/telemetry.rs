@@ -0,0 +1,108 @@
+//! `tracing` instrumentation of the calls, enabled with the
+//! `tracing` feature. Without it, the `record_*` functions are no-ops.
+//!
+//! Every call runs in a `murray.request` span, named after the
+//! OpenTelemetry conventions so that it can be exported as is
+//! with `tracing-opentelemetry`.
+
+use std::time::Duration;
+
+use reqwest::StatusCode;
+
+use crate::error::{MurrayError, Result};
+
+#[cfg(feature = "tracing")]
+pub(crate) fn span(
+  service: crate::Service,
+  request: &crate::transport::Request,
+  target: &str,
+  redact_addresses: bool,
+) -> tracing::Span {
+  let (path, query) = match target.split_once('?') {
+    Some((path, query)) => (path, Some(query)),
+    None => (target, None),
+  };
+  let path = if redact_addresses && request.endpoint.contains("{address}") {
+    request.endpoint
+  } else {
+    path
+  };
+
+  tracing::info_span!(
+    "murray.request",
+    otel.name = %format_args!("{} {}", request.method, request.endpoint),
+    otel.kind = "client",
+    otel.status_code = tracing::field::Empty,
+    murray.service = %service,
+    murray.endpoint = request.endpoint,
+    http.request.method = %request.method,
+    url.path = path,
+    url.query = query,
+    http.response.status_code = tracing::field::Empty,
+    murray.attempts = tracing::field::Empty,
+    murray.cache = tracing::field::Empty,
+    murray.latency_ms = tracing::field::Empty,
+    error.type = tracing::field::Empty,
+  )
+}
+
+/// Records whether the response was served by the in-memory cache.
+pub(crate) fn record_cache(hit: bool) {
+  #[cfg(feature = "tracing")]
+  tracing::Span::current().record("murray.cache", if hit { "hit" } else { "miss" });
+  #[cfg(not(feature = "tracing"))]
+  let _ = hit;
+}
+
+/// Records the status of the response to the latest attempt.
+pub(crate) fn record_status(status: StatusCode) {
+  #[cfg(feature = "tracing")]
+  tracing::Span::current().record("http.response.status_code", status.as_u16());
+  #[cfg(not(feature = "tracing"))]
+  let _ = status;
+}
+
+/// Records that `attempt` failed with `error`,
+/// and will be retried after `wait`.
+pub(crate) fn record_retry(attempt: u32, error: &MurrayError, wait: Duration) {
+  #[cfg(feature = "tracing")]
+  tracing::debug!(
+    attempt,
+    error = %error,
+    wait_ms = wait.as_millis() as u64,
+    "retrying Murray call"
+  );
+  #[cfg(not(feature = "tracing"))]
+  let _ = (attempt, error, wait);
+}
+
+/// Records how many attempts were sent, failed over or retried.
+pub(crate) fn record_attempts(attempts: u32) {
+  #[cfg(feature = "tracing")]
+  tracing::Span::current().record("murray.attempts", attempts);
+  #[cfg(not(feature = "tracing"))]
+  let _ = attempts;
+}
+
+/// Records the outcome of the whole call, and how long it took.
+pub(crate) fn record_outcome<T>(result: &Result<T>, elapsed: Duration) {
+  #[cfg(feature = "tracing")]
+  {
+    let span = tracing::Span::current();
+    span.record("murray.latency_ms", elapsed.as_secs_f64() * 1000.0);
+    match result {
+      Ok(_) => {
+        span.record("otel.status_code", "OK");
+      }
+      Err(error) => {
+        span.record("otel.status_code", "ERROR");
+        span.record("error.type", tracing::field::display(error.kind()));
+        if let Some(status) = error.status() {
+          span.record("http.response.status_code", status.as_u16());
+        }
+      }
+    }
+  }
+  #[cfg(not(feature = "tracing"))]
+  let _ = (result, elapsed);
+}
@@ -0,0 +1,612 @@
+use std::{
+  collections::HashMap,
+  future::{pending, Future},
+  sync::{Arc, Mutex},
+  time::{Duration, Instant},
+};
+
+use reqwest::{
+  header::{HeaderMap, HeaderValue, ACCEPT},
+  Method, StatusCode, Url,
+};
+use serde::de::DeserializeOwned;
+use tower::ServiceExt;
+
+use crate::{
+  auth::Auth,
+  cache::{Cache, Cacheability},
+  circuit_breaker::{Admission, CircuitBreaker, CircuitBreakerConfig, CircuitState},
+  error::{ErrorKind, MurrayError, Result},
+  failover::{self, Endpoints, FailoverPolicy},
+  metrics::Metrics,
+  middleware::HttpService,
+  options::RequestOptions,
+  rate_limit::{RateLimit, RateLimiter},
+  response::{CacheStatus, RawResponse, Response},
+  retry::{self, RetryPolicy},
+  single_flight::SingleFlight,
+  telemetry, Service,
+};
+
+/// Route template of the calls to endpoints the crate doesn't
+/// model, so that their metrics and errors share a single label.
+pub(crate) const CUSTOM_ENDPOINT: &str = "*";
+
+/// A call to one of the endpoints of a Murray service.
+pub(crate) struct Request {
+  pub method: Method,
+  /// Route template used to identify the endpoint, e.g. `/tx/{txid}`.
+  pub endpoint: &'static str,
+  /// Actual path requested, e.g. `/tx/15e1...`.
+  pub path: String,
+  pub query: Vec<(String, String)>,
+  pub body: Option<String>,
+  pub cacheability: Cacheability,
+}
+
+impl Request {
+  pub fn new(method: Method, endpoint: &'static str) -> Self {
+    Self {
+      method,
+      endpoint,
+      path: endpoint.to_string(),
+      query: vec![],
+      body: None,
+      cacheability: Cacheability::Volatile,
+    }
+  }
+
+  pub fn get(endpoint: &'static str) -> Self {
+    Self::new(Method::GET, endpoint)
+  }
+
+  pub fn post(endpoint: &'static str) -> Self {
+    Self::new(Method::POST, endpoint)
+  }
+
+  /// A GET of `path`, an endpoint the crate doesn't model.
+  pub fn custom(path: &str, query: &[(&str, &str)]) -> Self {
+    let path = match path.starts_with('/') {
+      true => path.to_string(),
+      false => format!("/{}", path),
+    };
+    let query = query.iter().map(|(k, v)| (*k, v.to_string())).collect();
+
+    Self::get(CUSTOM_ENDPOINT).path(path).query(query)
+  }
+
+  pub fn path(mut self, path: String) -> Self {
+    self.path = path;
+    self
+  }
+
+  pub fn query<K: Into<String>>(mut self, query: Vec<(K, String)>) -> Self {
+    self.query = query.into_iter().map(|(k, v)| (k.into(), v)).collect();
+    self
+  }
+
+  pub fn body(mut self, body: String) -> Self {
+    self.body = Some(body);
+    self
+  }
+
+  /// Marks the response as never changing once served.
+  pub fn immutable(mut self) -> Self {
+    self.cacheability = Cacheability::Immutable;
+    self
+  }
+
+  /// Marks the response, a transaction, as never
+  /// changing once it has been confirmed.
+  pub fn immutable_once_confirmed(mut self) -> Self {
+    self.cacheability = Cacheability::ImmutableOnceConfirmed;
+    self
+  }
+}
+
+/// Sends [`Request`]s to one Murray service and decodes
+/// their responses, turning every failure into a [`MurrayError`].
+#[derive(Clone)]
+pub(crate) struct Transport {
+  service: Service,
+  endpoints: Arc<Endpoints>,
+  http: HttpService,
+  retry_policy: Arc<RetryPolicy>,
+  rate_limiter: Option<Arc<RateLimiter>>,
+  circuit_breaker: Option<Arc<CircuitBreaker>>,
+  /// Circuit breakers of the base URLs given in [`RequestOptions`],
+  /// shared by all the calls overriding the base URL with the same one.
+  base_url_breakers: Arc<Mutex<HashMap<String, Arc<CircuitBreaker>>>>,
+  cache: Option<Cache>,
+  single_flight: Option<SingleFlight>,
+  metrics: Metrics,
+  auth: Option<Auth>,
+  options: RequestOptions,
+  #[cfg(feature = "tracing")]
+  redact_addresses: bool,
+}
+
+impl Transport {
+  pub fn new(service: Service, base_url: String, http: HttpService) -> Self {
+    Self {
+      service,
+      endpoints: Arc::new(Endpoints::new(vec![base_url], FailoverPolicy::default())),
+      http,
+      retry_policy: Arc::new(RetryPolicy::default()),
+      rate_limiter: None,
+      circuit_breaker: None,
+      base_url_breakers: Arc::default(),
+      cache: None,
+      single_flight: Some(SingleFlight::default()),
+      metrics: Metrics::default(),
+      auth: None,
+      options: RequestOptions::default(),
+      #[cfg(feature = "tracing")]
+      redact_addresses: false,
+    }
+  }
+
+  pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+    self.retry_policy = Arc::new(retry_policy);
+    self
+  }
+
+  pub fn with_rate_limit(mut self, rate_limit: Option<RateLimit>) -> Self {
+    self.rate_limiter = rate_limit.map(|limit| Arc::new(RateLimiter::new(limit)));
+    self
+  }
+
+  pub fn with_circuit_breaker(mut self, config: Option<CircuitBreakerConfig>) -> Self {
+    self.circuit_breaker = config.map(|config| Arc::new(CircuitBreaker::new(config)));
+    self
+  }
+
+  pub fn circuit_state(&self) -> Option<CircuitState> {
+    self.circuit_breaker.as_ref().map(|breaker| breaker.state())
+  }
+
+  pub fn with_cache(mut self, cache: Option<Cache>) -> Self {
+    self.cache = cache;
+    self
+  }
+
+  pub fn with_metrics(mut self, metrics: Metrics) -> Self {
+    self.metrics = metrics;
+    self
+  }
+
+  pub fn with_auth(mut self, auth: Option<Auth>) -> Self {
+    self.auth = auth;
+    self
+  }
+
+  /// Whether identical GET requests sent at the same
+  /// time share one call (enabled by default).
+  pub fn with_single_flight(mut self, enabled: bool) -> Self {
+    self.single_flight = enabled.then(SingleFlight::default);
+    self
+  }
+
+  /// Whether addresses are left out of the traced paths.
+  #[cfg(feature = "tracing")]
+  pub fn with_redacted_addresses(mut self, enabled: bool) -> Self {
+    self.redact_addresses = enabled;
+    self
+  }
+
+  /// Adds base URLs to fall back to when the current one is
+  /// down, and how to pick between them.
+  pub fn with_fallbacks(mut self, fallbacks: Vec<String>, policy: FailoverPolicy) -> Self {
+    let mut urls = vec![self.endpoints.url(0).to_string()];
+    urls.extend(fallbacks);
+    self.endpoints = Arc::new(Endpoints::new(urls, policy));
+    self
+  }
+
+  /// Applies `options` to the calls, on top of the options
+  /// given before.
+  pub fn with_options(mut self, options: RequestOptions) -> Self {
+    if let Some(retry_policy) = &options.retry_policy {
+      self.retry_policy = Arc::new(retry_policy.clone());
+    }
+    if let Some(base_url) = &options.base_url {
+      self.set_base_url(base_url.clone());
+      // the failures of another base URL say nothing about the usual ones
+      self.circuit_breaker = self.circuit_breaker.as_ref().map(|breaker| {
+        let mut breakers = self
+          .base_url_breakers
+          .lock()
+          .unwrap_or_else(|e| e.into_inner());
+        let config = breaker.config();
+        breakers
+          .entry(base_url.clone())
+          .or_insert_with(|| Arc::new(CircuitBreaker::new(config.clone())))
+          .clone()
+      });
+    }
+    self.options = self.options.merge(options);
+    self
+  }
+
+  /// Whether the calls skip the caches, see [`RequestOptions::bypass_cache`].
+  #[cfg(feature = "disk-cache")]
+  pub fn bypasses_cache(&self) -> bool {
+    self.options.bypass_cache
+  }
+
+  /// Replaces all the base URLs with `base_url`.
+  pub fn set_base_url(&mut self, base_url: String) {
+    let policy = self.endpoints.policy().clone();
+    self.endpoints = Arc::new(Endpoints::new(vec![base_url], policy));
+  }
+
+  /// Sends `request`, returning its decoded response with its metadata.
+  pub async fn send<T: DeserializeOwned>(&self, request: Request) -> Result<Response<T>> {
+    let target = self.target(&request)?;
+    #[cfg(feature = "tracing")]
+    let span = telemetry::span(self.service, &request, &target, self.redact_addresses);
+
+    let sent = async {
+      let started = Instant::now();
+      let endpoint = request.endpoint;
+      let fetched = self.fetch(request, target, started);
+      let result = self.bounded(endpoint, started, fetched).await;
+      telemetry::record_outcome(&result, started.elapsed());
+      result
+    };
+
+    #[cfg(feature = "tracing")]
+    let sent = tracing::Instrument::instrument(sent, span);
+    sent.await
+  }
+
+  /// The response to `request` served by the disk cache.
+  #[cfg(feature = "disk-cache")]
+  pub fn stored<T: serde::Serialize>(&self, request: &Request, data: T) -> Result<Response<T>> {
+    #[derive(serde::Serialize)]
+    struct JsonData<'a, T> {
+      data: &'a T,
+    }
+
+    let body = match serde_json::to_vec(&JsonData { data: &data }) {
+      Ok(body) => body,
+      Err(e) => {
+        return Err(
+          MurrayError::new(ErrorKind::JSONParse, self.service, request.endpoint).with_source(e),
+        )
+      }
+    };
+    let raw = RawResponse {
+      status: StatusCode::OK,
+      headers: HeaderMap::new(),
+      url: self.url(self.endpoints.url(0), request)?,
+      body: body.into(),
+    };
+    Ok(Response::new(data, raw, Duration::ZERO, CacheStatus::Hit))
+  }
+
+  /// Runs `call`, unless it is still running at the deadline
+  /// of the options or they get cancelled before it's over.
+  async fn bounded<T>(
+    &self,
+    endpoint: &'static str,
+    started: Instant,
+    call: impl Future<Output = Result<T>>,
+  ) -> Result<T> {
+    let cancelled = async {
+      match &self.options.cancellation_token {
+        Some(token) => token.cancelled().await,
+        None => pending().await,
+      }
+    };
+    let expired = async {
+      match self.options.deadline_of(started) {
+        Some(deadline) => tokio::time::sleep_until(deadline.into()).await,
+        None => pending().await,
+      }
+    };
+
+    let (kind, status) = tokio::select! {
+      // checked first, so that nothing is sent once cancelled
+      biased;
+      _ = cancelled => (ErrorKind::Cancelled, "cancelled"),
+      _ = expired => (ErrorKind::DeadlineExceeded, "timeout"),
+      result = call => return result,
+    };
+    self
+      .metrics
+      .record(self.service, endpoint, status, started.elapsed());
+    Err(MurrayError::new(kind, self.service, endpoint))
+  }
+
+  /// Gets the decoded response to `request`, from the cache or the service.
+  async fn fetch<T: DeserializeOwned>(
+    &self,
+    request: Request,
+    target: String,
+    started: Instant,
+  ) -> Result<Response<T>> {
+    let service = self.service;
+    let endpoint = request.endpoint;
+    let key = match &self.options.base_url {
+      // responses of another base URL may differ
+      Some(base_url) => format!("{} {}{}", service, base_url, target),
+      None => format!("{} {}", service, target),
+    };
+
+    let cache = self.cache.as_ref().filter(|cache| {
+      request.method == Method::GET && cache.may_cache(service, endpoint, request.cacheability)
+    });
+    let cached = cache
+      .filter(|_| !self.options.bypass_cache)
+      .and_then(|cache| cache.get(&key));
+    let from_cache = cached.is_some();
+    if cache.is_some() && !self.options.bypass_cache {
+      telemetry::record_cache(from_cache);
+    }
+
+    let raw = match cached {
+      Some(body) => RawResponse {
+        status: StatusCode::OK,
+        headers: HeaderMap::new(),
+        url: self.url(self.endpoints.url(0), &request)?,
+        body,
+      },
+      None => match &self.single_flight {
+        Some(single_flight) if request.method == Method::GET => {
+          single_flight
+            .run(key.clone(), || self.call(&request))
+            .await?
+        }
+        _ => self.call(&request).await?,
+      },
+    };
+
+    let data = match serde_json::from_slice::<T>(&raw.body) {
+      Ok(data) => data,
+      Err(e) => {
+        return Err(MurrayError::new(ErrorKind::JSONParse, service, endpoint).with_source(e))
+      }
+    };
+
+    if let (Some(cache), false) = (cache, from_cache) {
+      cache.insert(
+        key,
+        service,
+        endpoint,
+        target,
+        request.cacheability,
+        raw.body.clone(),
+      );
+    }
+
+    let cache_status = match (cache, from_cache) {
+      (None, _) => CacheStatus::Bypass,
+      (Some(_), _) if self.options.bypass_cache => CacheStatus::Bypass,
+      (Some(_), true) => CacheStatus::Hit,
+      (Some(_), false) => CacheStatus::Miss,
+    };
+    Ok(Response::new(data, raw, started.elapsed(), cache_status))
+  }
+
+  fn url(&self, base_url: &str, request: &Request) -> Result<Url> {
+    let url = format!("{}{}", base_url, request.path);
+
+    // `parse_with_params` would leave a dangling `?` without params
+    let url = if request.query.is_empty() {
+      Url::parse(&url)
+    } else {
+      Url::parse_with_params(&url, &request.query)
+    };
+
+    match url {
+      Ok(url) => Ok(url),
+      Err(e) => Err(
+        MurrayError::new(ErrorKind::InvalidURLParams, self.service, request.endpoint)
+          .with_source(e),
+      ),
+    }
+  }
+
+  /// Path and query of `request`, identifying it
+  /// whichever base URL it is sent to.
+  fn target(&self, request: &Request) -> Result<String> {
+    let url = self.url(self.endpoints.url(0), request)?;
+
+    Ok(match url.query() {
+      Some(query) => format!("{}?{}", request.path, query),
+      None => request.path.clone(),
+    })
+  }
+
+  /// Sends `request`, unless the circuit breaker is open,
+  /// and records whether the service answered.
+  async fn call(&self, request: &Request) -> Result<RawResponse> {
+    let Some(breaker) = &self.circuit_breaker else {
+      return self.execute(request).await;
+    };
+    let circuit_open = |retry_after| {
+      MurrayError::new(ErrorKind::CircuitOpen, self.service, request.endpoint)
+        .with_retry_after(Some(retry_after))
+    };
+
+    match breaker.admit() {
+      Admission::Allowed => {}
+      Admission::Probe => {
+        let base_url = self.endpoints.url(self.endpoints.select(&[]));
+        let healthy = failover::probe_health(&self.http, self.auth.as_ref(), base_url)
+          .await
+          .is_some();
+        breaker.record_probe(healthy);
+        if !healthy {
+          self.metrics.record(
+            self.service,
+            request.endpoint,
+            "circuit_open",
+            Duration::ZERO,
+          );
+          return Err(circuit_open(breaker.open_duration()));
+        }
+      }
+      Admission::Rejected(retry_after) => {
+        self.metrics.record(
+          self.service,
+          request.endpoint,
+          "circuit_open",
+          Duration::ZERO,
+        );
+        return Err(circuit_open(retry_after));
+      }
+    }
+
+    let result = self.execute(request).await;
+    breaker.record(matches!(&result, Err(error) if is_service_failure(error)));
+    result
+  }
+
+  /// Sends `request`, failing over between base URLs and retrying
+  /// it as told by the [`FailoverPolicy`] and [`RetryPolicy`],
+  /// and returns its successful response.
+  async fn execute(&self, request: &Request) -> Result<RawResponse> {
+    let mut attempt = 1;
+    // base URLs which failed during this attempt
+    let mut failed = vec![];
+    let mut tries = 0;
+    let mut reauthenticated = false;
+
+    loop {
+      self
+        .endpoints
+        .probe_unhealthy(&self.http, self.auth.as_ref());
+      let index = self.endpoints.select(&failed);
+      let url = self.url(self.endpoints.url(index), request)?;
+      let started = Instant::now();
+
+      tries += 1;
+      telemetry::record_attempts(tries);
+      let error = match self.execute_once(request, &url).await {
+        Ok(response) => {
+          self.endpoints.record_success(index, started.elapsed());
+          return Ok(response);
+        }
+        Err(error) => error,
+      };
+
+      // the token may have been revoked, get a new one right away
+      if error.status() == Some(StatusCode::UNAUTHORIZED) && !reauthenticated {
+        if let Some(auth) = &self.auth {
+          if auth.invalidate().await {
+            reauthenticated = true;
+            continue;
+          }
+        }
+      }
+
+      if is_service_failure(&error) {
+        self.endpoints.record_failure(index);
+        failed.push(index);
+
+        // fail over right away, backing off only once all are down
+        if self.retry_policy.retries(&request.method) && self.endpoints.has_healthy(&failed) {
+          continue;
+        }
+      }
+      failed.clear();
+
+      match self.retry_policy.backoff(&request.method, attempt, &error) {
+        Some(wait) => {
+          telemetry::record_retry(attempt, &error, wait);
+          tokio::time::sleep(wait).await
+        }
+        None => return Err(error),
+      }
+
+      attempt += 1;
+    }
+  }
+
+  async fn execute_once(&self, request: &Request, url: &Url) -> Result<RawResponse> {
+    let service = self.service;
+    let endpoint = request.endpoint;
+
+    if let Some(limiter) = &self.rate_limiter {
+      if let Err(wait) = limiter.acquire().await {
+        self
+          .metrics
+          .record(service, endpoint, "rate_limited", Duration::ZERO);
+        return Err(
+          MurrayError::new(ErrorKind::RateLimited, service, endpoint).with_retry_after(Some(wait)),
+        );
+      }
+    }
+
+    let mut http_request = reqwest::Request::new(request.method.clone(), url.clone());
+    http_request
+      .headers_mut()
+      .insert(ACCEPT, HeaderValue::from_static("application/json"));
+    if let Some(body) = &request.body {
+      *http_request.body_mut() = Some(body.clone().into());
+    }
+    if let Some(auth) = &self.auth {
+      if let Err(e) = auth.apply(http_request.headers_mut()).await {
+        return Err(MurrayError::new(ErrorKind::Auth, service, endpoint).with_boxed_source(e));
+      }
+    }
+
+    let started = Instant::now();
+    let response = match self.http.clone().oneshot(http_request).await {
+      Ok(resp) => resp,
+      Err(e) => {
+        let timed_out = e
+          .downcast_ref::<reqwest::Error>()
+          .is_some_and(reqwest::Error::is_timeout);
+        let status = if timed_out {
+          "timeout"
+        } else {
+          "connection_error"
+        };
+        self
+          .metrics
+          .record(service, endpoint, status, started.elapsed());
+        return Err(MurrayError::new(ErrorKind::Request, service, endpoint).with_boxed_source(e));
+      }
+    };
+
+    let status = response.status();
+    telemetry::record_status(status);
+    let headers = response.headers().clone();
+    let retry_after = retry::retry_after(&headers);
+    let body = response.bytes().await;
+    self
+      .metrics
+      .record(service, endpoint, status.as_str(), started.elapsed());
+    let body = match body {
+      Ok(body) => body,
+      Err(e) => return Err(MurrayError::new(ErrorKind::Request, service, endpoint).with_source(e)),
+    };
+
+    if !status.is_success() {
+      let mut error =
+        MurrayError::api(service, endpoint, status, &body).with_retry_after(retry_after);
+      if let Some(auth) = &self.auth {
+        if let Some(secret) = auth.secret().await {
+          error = error.redact(&secret);
+        }
+      }
+      return Err(error);
+    }
+
+    Ok(RawResponse {
+      status,
+      headers,
+      url: url.clone(),
+      body,
+    })
+  }
+}
+
+/// Whether `error` tells that the service is down or struggling,
+/// rather than that the call itself was wrong or never sent.
+fn is_service_failure(error: &MurrayError) -> bool {
+  matches!(error.kind(), ErrorKind::Request | ErrorKind::API) && error.is_retryable()
+}
@@ -0,0 +1,228 @@
+mod common;
+
+use std::{
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+  time::Duration,
+};
+
+use httpmock::prelude::*;
+use murray_rs::{Auth, BoxError, ErrorKind, Murray, RetryPolicy, Service, Token};
+
+use common::{get_ticker_params, ticker_body};
+
+fn murray(server: &MockServer, auth: Auth) -> Murray {
+  Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(RetryPolicy::none())
+    .auth(Service::Prices, auth)
+    .build()
+    .unwrap()
+}
+
+/// A token provider handing out `token-1`, `token-2`...,
+/// each expiring after `expires_in`.
+fn numbered_tokens(calls: Arc<AtomicUsize>, expires_in: Duration) -> Auth {
+  Auth::token_provider(move || {
+    let calls = calls.clone();
+    async move {
+      let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+      Ok(Token::new(format!("token-{}", call)).expires_in(expires_in))
+    }
+  })
+}
+
+#[tokio::test]
+async fn auth_should_send_bearer_token() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/ticker")
+        .header("Authorization", "Bearer some-token");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = murray(&server, Auth::bearer("some-token"));
+
+  // act
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  mock.assert_async().await;
+  assert!(response.is_ok());
+}
+
+#[tokio::test]
+async fn auth_should_send_api_key_header_to_its_service_only() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/ticker")
+        .header("x-api-key", "some-key");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let health = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health").header_exists("x-api-key");
+      then.status(200).body(r#"{"data": {"message": "OK"}}"#);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .lightning_endpoint(server.base_url())
+    .auth(
+      Service::Prices,
+      Auth::api_key("x-api-key", "some-key").unwrap(),
+    )
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+  let _ = murray.lightning.get_health().await;
+
+  // assert
+  mock.assert_async().await;
+  health.assert_hits_async(0).await;
+  assert!(response.is_ok());
+}
+
+#[tokio::test]
+async fn auth_should_reuse_token_until_it_expires() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let calls = Arc::new(AtomicUsize::new(0));
+  let expired = murray(&server, numbered_tokens(calls.clone(), Duration::ZERO));
+  let murray = murray(
+    &server,
+    numbered_tokens(calls.clone(), Duration::from_secs(3600)),
+  );
+
+  // act
+  for _ in 0..3 {
+    murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  }
+  for _ in 0..2 {
+    expired
+      .prices
+      .get_ticker(get_ticker_params())
+      .await
+      .unwrap();
+  }
+
+  // assert
+  mock.assert_hits_async(5).await;
+  assert_eq!(calls.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn auth_should_refresh_token_when_unauthorized() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let revoked = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/ticker")
+        .header("Authorization", "Bearer token-1");
+      then.status(401).body(r#"{"message": "Invalid token"}"#);
+    })
+    .await;
+  let refreshed = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/ticker")
+        .header("Authorization", "Bearer token-2");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let calls = Arc::new(AtomicUsize::new(0));
+  let murray = murray(
+    &server,
+    numbered_tokens(calls.clone(), Duration::from_secs(3600)),
+  );
+
+  // act
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  assert!(response.is_ok());
+  revoked.assert_async().await;
+  refreshed.assert_async().await;
+  assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn auth_should_fail_when_token_provider_fails() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = murray(
+    &server,
+    Auth::token_provider(|| async { Err::<Token, BoxError>("identity provider down".into()) }),
+  );
+
+  // act
+  let error = murray
+    .prices
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap_err();
+
+  // assert
+  mock.assert_hits_async(0).await;
+  assert_eq!(error.kind(), ErrorKind::Auth);
+  assert!(!error.is_retryable());
+}
+
+#[tokio::test]
+async fn auth_should_redact_credentials() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then
+        .status(403)
+        .body(r#"{"message": "Key some-key is not allowed", "key": "some-key"}"#);
+    })
+    .await;
+  let auth = Auth::api_key("x-api-key", "some-key").unwrap();
+  let murray = murray(&server, auth.clone());
+
+  // act
+  let error = murray
+    .prices
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap_err();
+
+  // assert
+  assert_eq!(error.message(), Some("Key <redacted> is not allowed"));
+  assert_eq!(error.body().unwrap()["key"], "<redacted>");
+  assert!(!error.to_string().contains("some-key"));
+  assert!(!format!("{:?}", error).contains("some-key"));
+  assert!(!format!("{:?}", auth).contains("some-key"));
+  assert!(!format!("{:?}", Auth::bearer("some-token")).contains("some-token"));
+}
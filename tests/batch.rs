@@ -0,0 +1,102 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use httpmock::prelude::*;
+use murray_rs::{BlockRef, GetAddressParams, GetTransactionParams, Txid};
+
+use common::{murray, read_mock};
+
+fn txid(digit: char) -> Txid {
+  digit.to_string().repeat(64).parse().unwrap()
+}
+
+fn transaction_body(txid: Txid) -> String {
+  let mut transaction = read_mock("tests/mocks/blockchain/get-transaction.json");
+  transaction["txid"] = txid.to_string().into();
+  format!(r#"{{"data": {}}}"#, transaction)
+}
+
+#[tokio::test]
+async fn get_transactions_should_return_each_result_in_order() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let [a, b, c, d] = ['a', 'b', 'c', 'd'].map(txid);
+  for (txid, delay) in [(a, 150), (b, 0), (d, 50)] {
+    server
+      .mock_async(|when, then| {
+        when.method(GET).path(format!("/tx/{}", txid));
+        then
+          .status(200)
+          .delay(Duration::from_millis(delay))
+          .body(transaction_body(txid));
+      })
+      .await;
+  }
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}", c));
+      then
+        .status(404)
+        .body(r#"{"message": "Transaction not found"}"#);
+    })
+    .await;
+  let params = [a, b, c, d].map(|txid| GetTransactionParams { txid });
+
+  // act
+  let results = murray(&server.base_url())
+    .blockchain
+    .get_transactions(params, 4)
+    .await;
+
+  // assert
+  assert_eq!(results.len(), 4);
+  assert_eq!(results[0].as_ref().unwrap().transaction.txid, a);
+  assert_eq!(results[1].as_ref().unwrap().transaction.txid, b);
+  assert!(results[2].as_ref().unwrap_err().is_not_found());
+  assert_eq!(results[3].as_ref().unwrap().transaction.txid, d);
+}
+
+#[tokio::test]
+async fn get_blocks_should_respect_the_concurrency_limit() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let block = read_mock("tests/mocks/blockchain/block-response.json");
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/block");
+      then
+        .status(200)
+        .delay(Duration::from_millis(100))
+        .body(format!(r#"{{"data": {}}}"#, block));
+    })
+    .await;
+  let blocks = (1..=4).map(BlockRef::Height);
+
+  // act
+  let started = Instant::now();
+  let results = murray(&server.base_url())
+    .blockchain
+    .get_blocks(blocks, 2)
+    .await;
+
+  // assert
+  mock.assert_hits_async(4).await;
+  assert!(started.elapsed() >= Duration::from_millis(200));
+  assert!(results.iter().all(Result::is_ok));
+}
+
+#[tokio::test]
+async fn get_addresses_details_should_return_an_empty_batch_for_no_addresses() {
+  // arrange
+  let server = MockServer::start_async().await;
+
+  // act
+  let results = murray(&server.base_url())
+    .blockchain
+    .get_addresses_details(Vec::<GetAddressParams>::new(), 0)
+    .await;
+
+  // assert
+  assert!(results.is_empty());
+}
@@ -1,29 +1,34 @@
+mod common;
+
 use std::fs;
 
 use httpmock::{prelude::*, Method, Mock};
 use murray_rs::{
-  GetAddressParams, GetBlockParams, GetTransactionParams, Murray, PostTransactionParams,
+  BlockHash, BlockRef, GetAddressParams, GetTransactionParams, Murray, ParseHashError,
+  PostTransactionParams, Txid,
 };
 use serde_json::Value;
 
+use common::TXID;
+
 struct Sut {
   server: MockServer,
 }
 
 impl Sut {
-  fn new() -> Self {
-    let server = MockServer::start();
+  async fn new() -> Self {
+    let server = MockServer::start_async().await;
     Sut { server }
   }
 
-  fn from(
+  async fn from(
     &self,
     path: &str,
     status: u16,
     method: Method,
     req_body: &str,
     res_body: &str,
-  ) -> (Mock, Murray) {
+  ) -> (Mock<'_>, Murray) {
     // Create a mock on the server.
     let mock = match method {
       Method::GET
@@ -31,20 +36,30 @@ impl Sut {
       | Method::DELETE
       | Method::OPTIONS
       | Method::TRACE
-      | Method::CONNECT => self.server.mock(|when, then| {
-        when.method(method).path(path);
-        then
-          .status(status)
-          .header("content-type", "application/json")
-          .body(res_body);
-      }),
-      Method::POST | Method::PATCH | Method::PUT => self.server.mock(|when, then| {
-        when.method(method).path(path).body(req_body);
-        then
-          .status(status)
-          .header("content-type", "application/json")
-          .body(res_body);
-      }),
+      | Method::CONNECT => {
+        self
+          .server
+          .mock_async(|when, then| {
+            when.method(method).path(path);
+            then
+              .status(status)
+              .header("content-type", "application/json")
+              .body(res_body);
+          })
+          .await
+      }
+      Method::POST | Method::PATCH | Method::PUT => {
+        self
+          .server
+          .mock_async(|when, then| {
+            when.method(method).path(path).body(req_body);
+            then
+              .status(status)
+              .header("content-type", "application/json")
+              .body(res_body);
+          })
+          .await
+      }
     };
 
     let mut murray = Murray::default();
@@ -55,277 +70,378 @@ impl Sut {
 }
 
 /// GET BLOCK
-#[test]
-fn get_block_should_return_successfully() {
+#[tokio::test]
+async fn get_block_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/blockchain/block-response.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/block", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/block", 200, Method::GET, "", &body).await;
 
   // act
   let response = murray
     .blockchain
-    .get_block(GetBlockParams {
-      hash: Some("some_value".to_string()),
-      height: Some(50000),
-    })
+    .get_block(BlockRef::Height(50000))
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
-  assert_eq!(response.block.id, expected_response["id"]);
+  mock.assert_async().await;
+  assert_eq!(response.block.id.to_string(), expected_response["id"]);
 }
 
-#[test]
-fn get_block_should_return_successfully_when_no_params() {
+#[tokio::test]
+async fn get_block_should_return_successfully_when_no_params() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/blockchain/block-response.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/block", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/block", 200, Method::GET, "", &body).await;
+
+  // act
+  let response = murray.blockchain.get_block(BlockRef::Tip).await.unwrap();
+
+  // assert
+  mock.assert_async().await;
+  assert_eq!(response.block.id.to_string(), expected_response["id"]);
+}
+
+#[tokio::test]
+async fn get_block_should_decode_the_genesis_block_and_unknown_fields() {
+  // arrange
+  let expected_response =
+    fs::read_to_string("tests/mocks/blockchain/block-response.json").expect("Unable to read file");
+  let mut expected_response: Value =
+    serde_json::from_str(&expected_response).expect("Unable to parse");
+  expected_response["height"] = 0.into();
+  expected_response["previousblockhash"] = Value::Null;
+  expected_response["tx_count"] = 70_000.into();
+  expected_response["stale"] = false.into();
+  let body = format!(r#"{{"data":  {}}}"#, expected_response);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/block", 200, Method::GET, "", &body).await;
 
   // act
   let response = murray
     .blockchain
-    .get_block(GetBlockParams {
-      hash: None,
-      height: None,
+    .get_block(BlockRef::Height(0))
+    .await
+    .unwrap();
+
+  // assert
+  assert!(response.block.previousblockhash.is_none());
+  assert_eq!(response.block.tx_count, 70_000);
+  assert_eq!(response.block.extra["stale"], false);
+  assert!(response.extras.is_some());
+}
+
+#[tokio::test]
+async fn get_block_should_find_the_block_by_its_hash() {
+  // arrange
+  let expected_response =
+    fs::read_to_string("tests/mocks/blockchain/block-response.json").expect("Unable to read file");
+  let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
+  let hash: BlockHash = expected_response["id"].as_str().unwrap().parse().unwrap();
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/block")
+        .query_param("hash", hash.to_string());
+      then
+        .status(200)
+        .body(format!(r#"{{"data":  {}}}"#, expected_response));
     })
+    .await;
+  let mut murray = Murray::default();
+  murray.blockchain.set_base_url(server.base_url());
+
+  // act
+  let response = murray
+    .blockchain
+    .get_block(BlockRef::Hash(hash))
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
-  assert_eq!(response.block.id, expected_response["id"]);
+  mock.assert_async().await;
+  assert_eq!(response.block.id, hash);
+  assert_eq!(
+    response.block.previousblockhash.unwrap().to_string(),
+    expected_response["previousblockhash"]
+  );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_block_should_return_error_when_problem_with_server() {
+async fn get_block_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/block", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/block", 400, Method::GET, "", &body).await;
 
   // act
-  let _response = murray
-    .blockchain
-    .get_block(GetBlockParams {
-      hash: None,
-      height: None,
-    })
-    .unwrap();
+  let _response = murray.blockchain.get_block(BlockRef::Tip).await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_block_should_return_error_when_body_returns_wrong_json() {
+async fn get_block_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/block", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/block", 200, Method::GET, "", &body).await;
 
   // act
-  let _response = murray
-    .blockchain
-    .get_block(GetBlockParams {
-      hash: None,
-      height: None,
-    })
-    .unwrap();
+  let _response = murray.blockchain.get_block(BlockRef::Tip).await.unwrap();
 }
 
-/// GET BLOCK2TIME
 #[test]
-fn get_block2time_should_return_successfully() {
+fn txid_and_block_hash_should_only_parse_64_hex_characters() {
+  // arrange
+  let hex = "0000000000000000000070311B00c527b7605025f572e328dc399039328afb87";
+
+  // act
+  let hash: BlockHash = hex.parse().unwrap();
+  let txid = TXID.parse::<Txid>().unwrap();
+
+  // assert
+  assert_eq!(hash.to_string(), hex.to_lowercase());
+  assert_eq!(txid.to_string(), TXID);
+  assert_eq!(
+    serde_json::to_value(txid).unwrap(),
+    Value::String(TXID.to_string())
+  );
+  assert_eq!(
+    serde_json::from_value::<Txid>(Value::String(TXID.to_string())).unwrap(),
+    txid
+  );
+  assert_eq!(
+    "15e1".parse::<Txid>(),
+    Err(ParseHashError::InvalidLength(4))
+  );
+  assert_eq!(
+    TXID.replace('e', "g").parse::<Txid>(),
+    Err(ParseHashError::InvalidCharacter('g'))
+  );
+  assert!(serde_json::from_value::<BlockHash>(Value::String("some-hash".to_string())).is_err());
+}
+
+#[test]
+fn txid_should_not_parse_non_ascii_characters() {
+  // arrange
+  let hex = format!("é{}", &TXID[1..]);
+
+  // act
+  let txid = hex.parse::<Txid>();
+
+  // assert
+  assert_eq!(hex.chars().count(), 64);
+  assert_eq!(txid, Err(ParseHashError::InvalidCharacter('é')));
+  assert_eq!(
+    format!("{}ab", hex).parse::<Txid>(),
+    Err(ParseHashError::InvalidCharacter('é'))
+  );
+}
+
+/// GET BLOCK2TIME
+#[tokio::test]
+async fn get_block2time_should_return_successfully() {
   // arrange
   let expected_response = fs::read_to_string("tests/mocks/blockchain/block2time-response.json")
     .expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/block2time", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/block2time", 200, Method::GET, "", &body).await;
 
   // act
   let response = murray
     .blockchain
-    .get_block2time(GetBlockParams {
-      hash: Some("some_value".to_string()),
-      height: Some(50000),
-    })
+    .get_block2time(BlockRef::Height(50000))
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.block2time.height, expected_response["height"]);
 }
 
-#[test]
-fn get_block2time_should_return_successfully_when_no_params() {
+#[tokio::test]
+async fn get_block2time_should_return_successfully_when_no_params() {
   // arrange
   let expected_response = fs::read_to_string("tests/mocks/blockchain/block2time-response.json")
     .expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/block2time", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/block2time", 200, Method::GET, "", &body).await;
 
   // act
   let response = murray
     .blockchain
-    .get_block2time(GetBlockParams {
-      hash: None,
-      height: None,
-    })
+    .get_block2time(BlockRef::Tip)
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.block2time.height, expected_response["height"]);
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_block2time_should_return_error_when_problem_with_server() {
+async fn get_block2time_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/block2time", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/block2time", 400, Method::GET, "", &body).await;
 
   // act
   let _response = murray
     .blockchain
-    .get_block2time(GetBlockParams {
-      hash: None,
-      height: None,
-    })
+    .get_block2time(BlockRef::Tip)
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_block2time_should_return_error_when_body_returns_wrong_json() {
+async fn get_block2time_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/block2time", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/block2time", 200, Method::GET, "", &body).await;
 
   // act
   let _response = murray
     .blockchain
-    .get_block2time(GetBlockParams {
-      hash: None,
-      height: None,
-    })
+    .get_block2time(BlockRef::Tip)
+    .await
     .unwrap();
 }
 
 /// GET FEES RECOMMENDED
-#[test]
-fn get_fees_recommended_should_return_successfully() {
+#[tokio::test]
+async fn get_fees_recommended_should_return_successfully() {
   // arrange
   let expected_response = fs::read_to_string("tests/mocks/blockchain/fees-recommended.json")
     .expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/fees/recommended", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut
+    .from("/fees/recommended", 200, Method::GET, "", &body)
+    .await;
 
   // act
-  let response = murray.blockchain.get_fees_recommended().unwrap();
+  let response = murray.blockchain.get_fees_recommended().await.unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(
     response.fees_recommended.fastest_fee,
     expected_response["fastestFee"]
   );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_fees_recommended_should_return_error_when_problem_with_server() {
+async fn get_fees_recommended_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/fees/recommended", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/fees/recommended", 400, Method::GET, "", &body)
+    .await;
 
   // act
-  let _response = murray.blockchain.get_fees_recommended().unwrap();
+  let _response = murray.blockchain.get_fees_recommended().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_fees_recommended_should_return_error_when_body_returns_wrong_json() {
+async fn get_fees_recommended_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/fees/recommended", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/fees/recommended", 200, Method::GET, "", &body)
+    .await;
 
   // act
-  let _response = murray.blockchain.get_fees_recommended().unwrap();
+  let _response = murray.blockchain.get_fees_recommended().await.unwrap();
 }
 
 /// GET FEES MEMPOOL BLOCKS
-#[test]
-fn get_fees_mempool_blocks_should_return_successfully() {
+#[tokio::test]
+async fn get_fees_mempool_blocks_should_return_successfully() {
   // arrange
   let expected_response = fs::read_to_string("tests/mocks/blockchain/fees-mempool-blocks.json")
     .expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/fees/mempool-blocks", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut
+    .from("/fees/mempool-blocks", 200, Method::GET, "", &body)
+    .await;
 
   // act
-  let response = murray.blockchain.get_fees_mempool_blocks().unwrap();
+  let response = murray.blockchain.get_fees_mempool_blocks().await.unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(
     response[0].fees_mempool_blocks.total_fees,
     expected_response[0]["totalFees"]
   );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_fees_mempool_blocks_should_return_error_when_problem_with_server() {
+async fn get_fees_mempool_blocks_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/fees/mempool-blocks", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/fees/mempool-blocks", 400, Method::GET, "", &body)
+    .await;
 
   // act
-  let _response = murray.blockchain.get_fees_mempool_blocks().unwrap();
+  let _response = murray.blockchain.get_fees_mempool_blocks().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_fees_mempool_blocks_should_return_error_when_body_returns_wrong_json() {
+async fn get_fees_mempool_blocks_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/fees/mempool-blocks", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/fees/mempool-blocks", 200, Method::GET, "", &body)
+    .await;
 
   // act
-  let _response = murray.blockchain.get_fees_mempool_blocks().unwrap();
+  let _response = murray.blockchain.get_fees_mempool_blocks().await.unwrap();
 }
 
 /// GET ADDRESS DETAILS
-#[test]
-fn get_address_details_should_return_successfully() {
+#[tokio::test]
+async fn get_address_details_should_return_successfully() {
   // arrange
   let expected_response = fs::read_to_string("tests/mocks/blockchain/get-address-details.json")
     .expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/address/some-address", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut
+    .from("/address/some-address", 200, Method::GET, "", &body)
+    .await;
 
   // act
   let response = murray
@@ -333,23 +449,26 @@ fn get_address_details_should_return_successfully() {
     .get_address_details(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(
     response.address_details.mempool_stats.funded_txo_sum,
     expected_response["mempool_stats"]["funded_txo_sum"]
   );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_address_details_should_return_error_when_problem_with_server() {
+async fn get_address_details_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/address/some-address", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/address/some-address", 400, Method::GET, "", &body)
+    .await;
 
   // act
   let _response = murray
@@ -357,16 +476,19 @@ fn get_address_details_should_return_error_when_problem_with_server() {
     .get_address_details(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_address_details_should_return_error_when_body_returns_wrong_json() {
+async fn get_address_details_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/address/some-address", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/address/some-address", 200, Method::GET, "", &body)
+    .await;
 
   // act
   let _response = murray
@@ -374,19 +496,22 @@ fn get_address_details_should_return_error_when_body_returns_wrong_json() {
     .get_address_details(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 }
 
 /// GET ADDRESS TRANSACTIONS
-#[test]
-fn get_address_transactions_should_return_successfully() {
+#[tokio::test]
+async fn get_address_transactions_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/blockchain/get-address-txs.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/address/some-address/txs", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut
+    .from("/address/some-address/txs", 200, Method::GET, "", &body)
+    .await;
 
   // act
   let response = murray
@@ -394,23 +519,26 @@ fn get_address_transactions_should_return_successfully() {
     .get_address_transactions(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(
-    response[0].address_transactions.txid,
+    response[0].address_transactions.txid.to_string(),
     expected_response[0]["txid"]
   );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_address_transactions_should_return_error_when_problem_with_server() {
+async fn get_address_transactions_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/address/some-address/txs", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/address/some-address/txs", 400, Method::GET, "", &body)
+    .await;
 
   // act
   let _response = murray
@@ -418,16 +546,19 @@ fn get_address_transactions_should_return_error_when_problem_with_server() {
     .get_address_transactions(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_address_transactions_should_return_error_when_body_returns_wrong_json() {
+async fn get_address_transactions_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/address/some-address/txs", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/address/some-address/txs", 200, Method::GET, "", &body)
+    .await;
 
   // act
   let _response = murray
@@ -435,25 +566,28 @@ fn get_address_transactions_should_return_error_when_body_returns_wrong_json() {
     .get_address_transactions(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 }
 
 /// GET ADDRESS UTXOS
-#[test]
-fn get_address_utxos_should_return_successfully() {
+#[tokio::test]
+async fn get_address_utxos_should_return_successfully() {
   // arrange
   let expected_response = fs::read_to_string("tests/mocks/blockchain/get-address-utxos.json")
     .expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from(
-    "/address/some-address/txs/utxo",
-    200,
-    Method::GET,
-    "",
-    &body,
-  );
+  let sut = Sut::new().await;
+  let (mock, murray) = sut
+    .from(
+      "/address/some-address/txs/utxo",
+      200,
+      Method::GET,
+      "",
+      &body,
+    )
+    .await;
 
   // act
   let response = murray
@@ -461,26 +595,29 @@ fn get_address_utxos_should_return_successfully() {
     .get_address_utxos(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
-  assert_eq!(response[0].txid, expected_response[0]["txid"]);
+  mock.assert_async().await;
+  assert_eq!(response[0].txid.to_string(), expected_response[0]["txid"]);
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_address_utxos_should_return_error_when_problem_with_server() {
+async fn get_address_utxos_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from(
-    "/address/some-address/txs/utxo",
-    400,
-    Method::GET,
-    "",
-    &body,
-  );
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from(
+      "/address/some-address/txs/utxo",
+      400,
+      Method::GET,
+      "",
+      &body,
+    )
+    .await;
 
   // act
   let _response = murray
@@ -488,22 +625,25 @@ fn get_address_utxos_should_return_error_when_problem_with_server() {
     .get_address_utxos(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_address_utxos_should_return_error_when_body_returns_wrong_json() {
+async fn get_address_utxos_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from(
-    "/address/some-address/txs/utxo",
-    200,
-    Method::GET,
-    "",
-    &body,
-  );
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from(
+      "/address/some-address/txs/utxo",
+      200,
+      Method::GET,
+      "",
+      &body,
+    )
+    .await;
 
   // act
   let _response = murray
@@ -511,214 +651,269 @@ fn get_address_utxos_should_return_error_when_body_returns_wrong_json() {
     .get_address_utxos(GetAddressParams {
       address: "some-address".to_string(),
     })
+    .await
     .unwrap();
 }
 
 /// GET HASHRATE
-#[test]
-fn get_hashrate_should_return_successfully() {
+#[tokio::test]
+async fn get_hashrate_should_return_successfully() {
   // arrange
   let expected_response = fs::read_to_string("tests/mocks/blockchain/get-hashrate-response.json")
     .expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/hashrate", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/hashrate", 200, Method::GET, "", &body).await;
 
   // act
-  let response = murray.blockchain.get_hashrate().unwrap();
+  let response = murray.blockchain.get_hashrate().await.unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(
     response.hashrate.current_hashrate,
     expected_response["currentHashrate"]
   );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_hashrate_should_return_error_when_problem_with_server() {
+async fn get_hashrate_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/hashrate", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/hashrate", 400, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.blockchain.get_hashrate().unwrap();
+  let _response = murray.blockchain.get_hashrate().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_hashrate_should_return_error_when_body_returns_wrong_json() {
+async fn get_hashrate_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/hashrate", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/hashrate", 200, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.blockchain.get_hashrate().unwrap();
+  let _response = murray.blockchain.get_hashrate().await.unwrap();
 }
 
 /// GET HEALTH
-#[test]
-fn get_health_should_return_successfully() {
+#[tokio::test]
+async fn get_health_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/blockchain/get-health.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/health", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/health", 200, Method::GET, "", &body).await;
 
   // act
-  let response = murray.blockchain.get_health().unwrap();
+  let response = murray.blockchain.get_health().await.unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.message, expected_response["message"]);
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_health_should_return_error_when_problem_with_server() {
+async fn get_health_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/health", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/health", 400, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.blockchain.get_health().unwrap();
+  let _response = murray.blockchain.get_health().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_health_should_return_error_when_body_returns_wrong_json() {
+async fn get_health_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/health", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/health", 200, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.blockchain.get_health().unwrap();
+  let _response = murray.blockchain.get_health().await.unwrap();
 }
 
 /// GET MEMPOOL
-#[test]
-fn get_mempool_should_return_successfully() {
+#[tokio::test]
+async fn get_mempool_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/blockchain/get-mempool.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/mempool", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/mempool", 200, Method::GET, "", &body).await;
 
   // act
-  let response = murray.blockchain.get_mempool().unwrap();
+  let response = murray.blockchain.get_mempool().await.unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(
     response.mempool_response.total_fee,
     expected_response["total_fee"]
   );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_mempool_should_return_error_when_problem_with_server() {
+async fn get_mempool_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/mempool", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/mempool", 400, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.blockchain.get_mempool().unwrap();
+  let _response = murray.blockchain.get_mempool().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_mempool_should_return_error_when_body_returns_wrong_json() {
+async fn get_mempool_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/mempool", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/mempool", 200, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.blockchain.get_mempool().unwrap();
+  let _response = murray.blockchain.get_mempool().await.unwrap();
 }
 
 /// GET TRANSACTION
-#[test]
-fn get_transaction_should_return_successfully() {
+#[tokio::test]
+async fn get_transaction_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/blockchain/get-transaction.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/tx/some-tx-id", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut
+    .from(&format!("/tx/{}", TXID), 200, Method::GET, "", &body)
+    .await;
 
   // act
   let response = murray
     .blockchain
     .get_transaction(GetTransactionParams {
-      txid: "some-tx-id".to_string(),
+      txid: TXID.parse().unwrap(),
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
-  assert_eq!(response.transaction.txid, expected_response["txid"]);
+  mock.assert_async().await;
+  assert_eq!(
+    response.transaction.txid.to_string(),
+    expected_response["txid"]
+  );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_transaction_should_return_error_when_problem_with_server() {
+async fn get_transaction_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/tx/some-tx-id", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from(&format!("/tx/{}", TXID), 400, Method::GET, "", &body)
+    .await;
 
   // act
   let _response = murray
     .blockchain
     .get_transaction(GetTransactionParams {
-      txid: "some-tx-id".to_string(),
+      txid: TXID.parse().unwrap(),
     })
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_transaction_should_return_error_when_body_returns_wrong_json() {
+async fn get_transaction_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/tx/some-tx-id", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from(&format!("/tx/{}", TXID), 200, Method::GET, "", &body)
+    .await;
 
   // act
   let _response = murray
     .blockchain
     .get_transaction(GetTransactionParams {
-      txid: "some-tx-id".to_string(),
+      txid: TXID.parse().unwrap(),
     })
+    .await
     .unwrap();
 }
 
+#[tokio::test]
+async fn get_transaction_should_decode_coinbase_and_op_return_transactions() {
+  // arrange
+  let expected_response =
+    fs::read_to_string("tests/mocks/blockchain/get-transaction.json").expect("Unable to read file");
+  let mut expected_response: Value =
+    serde_json::from_str(&expected_response).expect("Unable to parse");
+  expected_response["vin"][0]["prevout"] = Value::Null;
+  expected_response["vin"][0]["is_coinbase"] = true.into();
+  expected_response["vout"][0]
+    .as_object_mut()
+    .unwrap()
+    .remove("scriptpubkey_address");
+  expected_response["fee"] = 5_000_000_000u64.into();
+  expected_response["vsize"] = 141.into();
+  let body = format!(r#"{{"data":  {}}}"#, expected_response);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from(&format!("/tx/{}", TXID), 200, Method::GET, "", &body)
+    .await;
+
+  // act
+  let response = murray
+    .blockchain
+    .get_transaction(GetTransactionParams {
+      txid: TXID.parse().unwrap(),
+    })
+    .await
+    .unwrap();
+
+  // assert
+  let transaction = &response.transaction;
+  assert!(transaction.vin[0].prevout.is_none());
+  assert!(transaction.vout[0].scriptpubkey_address.is_none());
+  assert_eq!(transaction.fee, 5_000_000_000);
+  assert_eq!(transaction.extra["vsize"], 141);
+  assert_eq!(
+    serde_json::to_value(&response).unwrap()["vsize"],
+    expected_response["vsize"]
+  );
+}
+
 /// POST TRANSACTION
-#[test]
-fn post_transaction_should_return_successfully() {
+#[tokio::test]
+async fn post_transaction_should_return_successfully() {
   // arrange
   let expected_response = fs::read_to_string("tests/mocks/blockchain/post-transaction.json")
     .expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
+  let sut = Sut::new().await;
   let tx_hex = "some-tx-hex";
   let req_body = format!(r#"{{"txHex":"{}"}}"#, tx_hex);
-  let (mock, murray) = sut.from("/tx", 200, Method::POST, &req_body, &body);
+  let (mock, murray) = sut.from("/tx", 200, Method::POST, &req_body, &body).await;
 
   // act
   let response = murray
@@ -726,22 +921,23 @@ fn post_transaction_should_return_successfully() {
     .post_transaction(PostTransactionParams {
       tx_hex: tx_hex.to_string(),
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
-  assert_eq!(response.txid, expected_response["txid"]);
+  mock.assert_async().await;
+  assert_eq!(response.txid.to_string(), expected_response["txid"]);
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn post_transaction_should_return_error_when_problem_with_server() {
+async fn post_transaction_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
+  let sut = Sut::new().await;
   let tx_hex = "some-tx-hex";
   let req_body = format!(r#"{{"txHex":"{}"}}"#, tx_hex);
-  let (_mock, murray) = sut.from("/tx", 400, Method::POST, &req_body, &body);
+  let (_mock, murray) = sut.from("/tx", 400, Method::POST, &req_body, &body).await;
 
   // act
   let _response = murray
@@ -749,18 +945,19 @@ fn post_transaction_should_return_error_when_problem_with_server() {
     .post_transaction(PostTransactionParams {
       tx_hex: tx_hex.to_string(),
     })
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn post_transaction_should_return_error_when_body_returns_wrong_json() {
+async fn post_transaction_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
+  let sut = Sut::new().await;
   let tx_hex = "some-tx-hex";
   let req_body = format!(r#"{{"txHex":"{}"}}"#, tx_hex);
-  let (_mock, murray) = sut.from("/tx", 200, Method::POST, &req_body, &body);
+  let (_mock, murray) = sut.from("/tx", 200, Method::POST, &req_body, &body).await;
 
   // act
   let _response = murray
@@ -768,5 +965,6 @@ fn post_transaction_should_return_error_when_body_returns_wrong_json() {
     .post_transaction(PostTransactionParams {
       tx_hex: tx_hex.to_string(),
     })
+    .await
     .unwrap();
 }
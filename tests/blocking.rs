@@ -0,0 +1,133 @@
+#![cfg(feature = "blocking")]
+
+mod common;
+
+use httpmock::prelude::*;
+use murray_rs::{
+  blocking::Murray, BlockRef, GetNodeDetailsParams, GetTickerParams, Service, Symbol,
+};
+
+use common::read_mock;
+
+#[test]
+fn blockchain_get_block_should_return_successfully() {
+  // arrange
+  let expected_response = read_mock("tests/mocks/blockchain/block-response.json");
+  let body = format!(r#"{{"data":  {}}}"#, expected_response);
+  let server = MockServer::start();
+  let mock = server.mock(|when, then| {
+    when.method(GET).path("/block");
+    then
+      .status(200)
+      .header("content-type", "application/json")
+      .body(&body);
+  });
+  let mut murray = Murray::default();
+  murray.blockchain.set_base_url(server.base_url());
+
+  // act
+  let response = murray.blockchain.get_block(BlockRef::Tip).unwrap();
+
+  // assert
+  mock.assert();
+  assert_eq!(response.block.id.to_string(), expected_response["id"]);
+}
+
+#[test]
+fn lightning_get_node_details_should_return_successfully() {
+  // arrange
+  let expected_response = read_mock("tests/mocks/lightning/node-details.json");
+  let body = format!(r#"{{"data":  {}}}"#, expected_response);
+  let server = MockServer::start();
+  let mock = server.mock(|when, then| {
+    when.method(GET).path("/node/some_key");
+    then
+      .status(200)
+      .header("content-type", "application/json")
+      .body(&body);
+  });
+  let mut murray = Murray::default();
+  murray.lightning.set_base_url(server.base_url());
+
+  // act
+  let response = murray
+    .lightning
+    .get_node_details(GetNodeDetailsParams {
+      public_key: "some_key".to_string(),
+    })
+    .unwrap();
+
+  // assert
+  mock.assert();
+  assert_eq!(response.alias, expected_response["alias"]);
+}
+
+#[test]
+fn prices_get_ticker_should_be_callable_repeatedly() {
+  // arrange
+  let expected_response = read_mock("tests/mocks/prices/get-ticker.json");
+  let body = format!(r#"{{"data":  {}}}"#, expected_response);
+  let server = MockServer::start();
+  let mock = server.mock(|when, then| {
+    when.method(GET).path("/ticker");
+    then
+      .status(200)
+      .header("content-type", "application/json")
+      .body(&body);
+  });
+  let mut murray = Murray::default();
+  murray.prices.set_base_url(server.base_url());
+
+  // act
+  for _ in 0..3 {
+    let response = murray
+      .prices
+      .get_ticker(GetTickerParams {
+        symbol: Symbol::BTCUSD,
+      })
+      .unwrap();
+    assert_eq!(response.price, expected_response["price"]);
+  }
+
+  // assert
+  mock.assert_hits(3);
+}
+
+#[test]
+#[should_panic]
+fn blockchain_get_health_should_return_error_when_problem_with_server() {
+  // arrange
+  let server = MockServer::start();
+  server.mock(|when, then| {
+    when.method(GET).path("/health");
+    then.status(500);
+  });
+  let mut murray = Murray::default();
+  murray.blockchain.set_base_url(server.base_url());
+
+  // act
+  let _response = murray.blockchain.get_health().unwrap();
+}
+
+#[test]
+fn call_json_should_return_the_data_of_the_response() {
+  // arrange
+  let server = MockServer::start();
+  server.mock(|when, then| {
+    when
+      .method(GET)
+      .path("/channels")
+      .query_param("public_key", "03abcd");
+    then.status(200).body(r#"{"data": [{"capacity": 100000}]}"#);
+  });
+  let mut murray = Murray::default();
+  murray.lightning.set_base_url(server.base_url());
+
+  // act
+  let response = murray
+    .call_json(Service::Lightning, "/channels", &[("public_key", "03abcd")])
+    .unwrap();
+
+  // assert
+  assert_eq!(response[0]["capacity"], 100000);
+}
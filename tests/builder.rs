@@ -0,0 +1,140 @@
+mod common;
+
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use murray_rs::{
+  reqwest::{
+    header::{HeaderMap, HeaderValue},
+    Client,
+  },
+  ErrorKind, Murray,
+};
+
+use common::body_from;
+
+#[tokio::test]
+async fn builder_should_send_user_agent_and_default_headers() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/health")
+        .header("user-agent", "my-app/1.0")
+        .header("x-tenant", "acme");
+      then
+        .status(200)
+        .header("content-type", "application/json")
+        .body(body_from("tests/mocks/blockchain/get-health.json"));
+    })
+    .await;
+  let mut headers = HeaderMap::new();
+  headers.insert("x-tenant", HeaderValue::from_static("acme"));
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .user_agent("my-app/1.0")
+    .default_headers(headers)
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray.blockchain.get_health().await;
+
+  // assert
+  mock.assert_async().await;
+  assert!(response.is_ok());
+}
+
+#[tokio::test]
+async fn builder_should_send_crate_user_agent_by_default() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health").header(
+        "user-agent",
+        concat!("murray-rs/", env!("CARGO_PKG_VERSION")),
+      );
+      then
+        .status(200)
+        .header("content-type", "application/json")
+        .body(body_from("tests/mocks/blockchain/get-health.json"));
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray.prices.get_health().await;
+
+  // assert
+  mock.assert_async().await;
+  assert!(response.is_ok());
+}
+
+#[tokio::test]
+async fn builder_should_return_timeout_error_when_service_hangs() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health");
+      then
+        .status(200)
+        .header("content-type", "application/json")
+        .body(body_from("tests/mocks/blockchain/get-health.json"))
+        .delay(Duration::from_secs(2));
+    })
+    .await;
+  let murray = Murray::builder()
+    .lightning_endpoint(server.base_url())
+    .timeout(Duration::from_millis(200))
+    .build()
+    .unwrap();
+
+  // act
+  let error = murray.lightning.get_health().await.unwrap_err();
+
+  // assert
+  assert_eq!(error.kind(), ErrorKind::Request);
+  assert!(error.is_timeout());
+  assert!(error.is_retryable());
+}
+
+#[tokio::test]
+async fn builder_should_use_injected_client_for_every_service() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health").header("x-injected", "yes");
+      then
+        .status(200)
+        .header("content-type", "application/json")
+        .body(body_from("tests/mocks/blockchain/get-health.json"));
+    })
+    .await;
+  let mut headers = HeaderMap::new();
+  headers.insert("x-injected", HeaderValue::from_static("yes"));
+  let client = Client::builder().default_headers(headers).build().unwrap();
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .prices_endpoint(server.base_url())
+    .lightning_endpoint(server.base_url())
+    .client(client)
+    .build()
+    .unwrap();
+
+  // act
+  let blockchain = murray.blockchain.get_health().await;
+  let prices = murray.prices.get_health().await;
+  let lightning = murray.lightning.get_health().await;
+
+  // assert
+  mock.assert_hits_async(3).await;
+  assert!(blockchain.is_ok() && prices.is_ok() && lightning.is_ok());
+}
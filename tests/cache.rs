@@ -0,0 +1,237 @@
+mod common;
+
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use murray_rs::{
+  BlockRef, CacheConfig, CacheTtl, GetTickerParams, GetTransactionParams, Murray, Service, Symbol,
+};
+
+use common::{body_from, get_ticker_params, read_mock, ticker_body};
+
+fn unconfirmed_transaction_body() -> String {
+  let mut expected_response = read_mock("tests/mocks/blockchain/get-transaction.json");
+  expected_response["status"] = serde_json::json!({ "confirmed": false });
+  format!(r#"{{"data":  {}}}"#, expected_response)
+}
+
+#[tokio::test]
+async fn cache_should_serve_repeated_calls_from_memory() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default())
+    .build()
+    .unwrap();
+
+  // act
+  let first = murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  let second = murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+
+  // assert
+  mock.assert_hits_async(1).await;
+  assert_eq!(first.price, second.price);
+  let stats = murray.cache().unwrap().stats();
+  assert_eq!((stats.hits, stats.misses, stats.entries), (1, 1, 1));
+  assert_eq!(stats.hit_rate(), 0.5);
+}
+
+#[tokio::test]
+async fn cache_should_be_disabled_by_default() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  for _ in 0..2 {
+    murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  }
+
+  // assert
+  mock.assert_hits_async(2).await;
+  assert!(murray.cache().is_none());
+}
+
+#[tokio::test]
+async fn cache_should_expire_entries_after_their_ttl() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default().ttl(
+      Service::Prices,
+      "/ticker",
+      CacheTtl::For(Duration::from_millis(50)),
+    ))
+    .build()
+    .unwrap();
+
+  // act
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+
+  // assert
+  mock.assert_hits_async(2).await;
+  let stats = murray.cache().unwrap().stats();
+  assert_eq!((stats.entries, stats.evictions), (1, 1));
+}
+
+#[tokio::test]
+async fn cache_should_keep_blocks_by_height_even_when_endpoint_ttl_is_disabled() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/block");
+      then
+        .status(200)
+        .body(body_from("tests/mocks/blockchain/block-response.json"));
+    })
+    .await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .cache(CacheConfig::default().ttl(Service::Blockchain, "/block", CacheTtl::Disabled))
+    .build()
+    .unwrap();
+
+  // act
+  for block in [BlockRef::Height(500_000); 2] {
+    murray.blockchain.get_block(block).await.unwrap();
+  }
+  for block in [BlockRef::Tip; 2] {
+    murray.blockchain.get_block(block).await.unwrap();
+  }
+
+  // assert
+  // the tip is fetched every time, the block at a given height only once
+  mock.assert_hits_async(3).await;
+}
+
+#[tokio::test]
+async fn cache_should_only_keep_transactions_forever_once_confirmed() {
+  // arrange
+  let confirmed_txid = "a".repeat(64);
+  let unconfirmed_txid = "b".repeat(64);
+  let server = MockServer::start_async().await;
+  let confirmed = server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}", confirmed_txid));
+      then
+        .status(200)
+        .body(body_from("tests/mocks/blockchain/get-transaction.json"));
+    })
+    .await;
+  let unconfirmed = server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}", unconfirmed_txid));
+      then.status(200).body(unconfirmed_transaction_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .cache(CacheConfig::default().ttl(Service::Blockchain, "/tx/{txid}", CacheTtl::Disabled))
+    .build()
+    .unwrap();
+
+  // act
+  for txid in [
+    &confirmed_txid,
+    &unconfirmed_txid,
+    &confirmed_txid,
+    &unconfirmed_txid,
+  ] {
+    murray
+      .blockchain
+      .get_transaction(GetTransactionParams {
+        txid: txid.parse().unwrap(),
+      })
+      .await
+      .unwrap();
+  }
+
+  // assert
+  confirmed.assert_hits_async(1).await;
+  unconfirmed.assert_hits_async(2).await;
+}
+
+#[tokio::test]
+async fn cache_should_refetch_after_invalidation() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default())
+    .build()
+    .unwrap();
+  let cache = murray.cache().unwrap();
+
+  // act
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  cache.invalidate_path(Service::Prices, "/ticker?symbol=BTCUSD");
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  cache.invalidate_service(Service::Prices);
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+
+  // assert
+  mock.assert_hits_async(3).await;
+  assert_eq!(cache.stats().entries, 1);
+}
+
+#[tokio::test]
+async fn cache_should_evict_least_recently_used_entries() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default().max_entries(2))
+    .build()
+    .unwrap();
+
+  // act
+  for symbol in [Symbol::BTCUSD, Symbol::BTCBRL, Symbol::BTCUSDT] {
+    murray
+      .prices
+      .get_ticker(GetTickerParams { symbol })
+      .await
+      .unwrap();
+  }
+
+  // assert
+  let stats = murray.cache().unwrap().stats();
+  assert_eq!((stats.entries, stats.evictions), (2, 1));
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use httpmock::prelude::*;
+use murray_rs::{ErrorKind, Service};
+use serde::Deserialize;
+use serde_json::json;
+
+use common::{murray, TXID};
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Outspend {
+  spent: bool,
+  vin: Option<u32>,
+}
+
+#[tokio::test]
+async fn call_json_should_return_the_data_of_the_response() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/history")
+        .query_param("symbol", "BTCUSD")
+        .query_param("days", "7");
+      then
+        .status(200)
+        .body(r#"{"data": {"prices": [1, 2.5], "unknown": null}}"#);
+    })
+    .await;
+
+  // act
+  let data = murray(&server.base_url())
+    .call_json(
+      Service::Prices,
+      "/history",
+      &[("symbol", "BTCUSD"), ("days", "7")],
+    )
+    .await
+    .unwrap();
+
+  // assert
+  mock.assert_async().await;
+  assert_eq!(data, json!({"prices": [1, 2.5], "unknown": null}));
+}
+
+#[tokio::test]
+async fn call_should_decode_the_data_of_the_response() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}/outspends", TXID));
+      then
+        .status(200)
+        .body(r#"{"data": [{"spent": true, "vin": 0}, {"spent": false}]}"#);
+    })
+    .await;
+
+  // act
+  let outspends: Vec<Outspend> = murray(&server.base_url())
+    .call(Service::Blockchain, &format!("tx/{}/outspends", TXID), &[])
+    .await
+    .unwrap();
+
+  // assert
+  assert_eq!(
+    outspends,
+    vec![
+      Outspend {
+        spent: true,
+        vin: Some(0)
+      },
+      Outspend {
+        spent: false,
+        vin: None
+      }
+    ]
+  );
+}
+
+#[tokio::test]
+async fn call_should_label_its_errors_with_the_custom_endpoint() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/missing");
+      then.status(404).body(r#"{"message": "Not Found"}"#);
+    })
+    .await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/unexpected");
+      then.status(200).body(r#"{"data": {"spent": "yes"}}"#);
+    })
+    .await;
+  let murray = murray(&server.base_url());
+
+  // act
+  let not_found = murray
+    .call_json(Service::Blockchain, "/missing", &[])
+    .await
+    .unwrap_err();
+  let undecodable = murray
+    .call::<Outspend>(Service::Blockchain, "/unexpected", &[])
+    .await
+    .unwrap_err();
+
+  // assert
+  assert_eq!(not_found.kind(), ErrorKind::API);
+  assert_eq!(not_found.service(), Service::Blockchain);
+  assert_eq!(not_found.endpoint(), "*");
+  assert_eq!(not_found.message(), Some("Not Found"));
+  assert_eq!(undecodable.kind(), ErrorKind::JSONParse);
+  assert_eq!(undecodable.endpoint(), "*");
+  let metrics = murray.metrics();
+  let counts: Vec<_> = metrics
+    .requests
+    .iter()
+    .map(|m| (m.endpoint, m.status.as_str(), m.count))
+    .collect();
+  assert_eq!(counts, vec![("*", "200", 1), ("*", "404", 1)]);
+}
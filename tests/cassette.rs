@@ -0,0 +1,210 @@
+#![cfg(feature = "test-utils")]
+
+mod common;
+
+use std::{error::Error, fs, path::Path};
+
+use httpmock::prelude::*;
+use murray_rs::{
+  Cassette, CassetteMode, ErrorKind, GetAddressParams, Murray, PostTransactionParams, RetryPolicy,
+};
+
+use common::{get_ticker_params, ticker_body};
+
+/// An address no request can reach, to make sure replays stay offline.
+const OFFLINE: &str = "http://127.0.0.1:9";
+
+const ADDRESS: &str = "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh";
+
+fn address_details() -> String {
+  fs::read_to_string("tests/mocks/blockchain/get-address-details.json")
+    .expect("Unable to read file")
+    .replace("1wiz18xYmhRX6xStj2b9t1rwWX4GKUgpv", ADDRESS)
+}
+
+fn murray(url: &str, cassette: Cassette) -> Murray {
+  Murray::builder()
+    .blockchain_endpoint(url)
+    .prices_endpoint(url)
+    .retry_policy(RetryPolicy::none())
+    .cassette(cassette)
+    .build()
+    .unwrap()
+}
+
+fn open(path: &Path, mode: CassetteMode) -> Cassette {
+  Cassette::open(path, mode).unwrap()
+}
+
+#[tokio::test]
+async fn cassette_should_replay_recorded_responses_offline() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let ticker = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/ticker")
+        .query_param("symbol", "BTCUSD");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let post = server
+    .mock_async(|when, then| {
+      when.method(POST).path("/tx").body(r#"{"txHex":"0100"}"#);
+      then
+        .status(400)
+        .body(r#"{"message": "bad-txns-inputs-missingorspent"}"#);
+    })
+    .await;
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("cassettes/murray.json");
+  let post_params = || PostTransactionParams {
+    tx_hex: "0100".to_string(),
+  };
+
+  let recording = murray(&server.base_url(), open(&path, CassetteMode::Record));
+  let recorded_ticker = recording.prices.get_ticker(get_ticker_params()).await;
+  let recorded_error = recording
+    .blockchain
+    .post_transaction(post_params())
+    .await
+    .unwrap_err();
+
+  // act
+  let replaying = murray(OFFLINE, open(&path, CassetteMode::Replay));
+  let replayed_ticker = replaying.prices.get_ticker(get_ticker_params()).await;
+  let replayed_error = replaying
+    .blockchain
+    .post_transaction(post_params())
+    .await
+    .unwrap_err();
+
+  // assert
+  ticker.assert_hits_async(1).await;
+  post.assert_hits_async(1).await;
+  assert_eq!(
+    format!("{:?}", recorded_ticker.unwrap()),
+    format!("{:?}", replayed_ticker.unwrap())
+  );
+  assert_eq!(recorded_error.status(), replayed_error.status());
+  assert_eq!(
+    replayed_error.message(),
+    Some("bad-txns-inputs-missingorspent")
+  );
+}
+
+#[tokio::test]
+async fn cassette_should_fail_unrecorded_requests_when_replaying() {
+  // arrange
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("murray.json");
+  fs::write(&path, r#"{"interactions": []}"#).unwrap();
+  let murray = murray(OFFLINE, open(&path, CassetteMode::Replay));
+
+  // act
+  let error = murray
+    .prices
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap_err();
+
+  // assert
+  assert_eq!(error.kind(), ErrorKind::Request);
+  assert!(error
+    .source()
+    .unwrap()
+    .to_string()
+    .starts_with("no recorded response for prices GET /ticker?symbol=BTCUSD"));
+  assert!(Cassette::open(dir.path().join("missing.json"), CassetteMode::Replay).is_err());
+}
+
+#[tokio::test]
+async fn cassette_should_only_send_new_requests_when_recording_new() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let ticker = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let health = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health");
+      then.status(200).body(r#"{"data": {"message": "OK"}}"#);
+    })
+    .await;
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("murray.json");
+  let recording = murray(&server.base_url(), open(&path, CassetteMode::Record));
+  recording
+    .prices
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap();
+
+  // act
+  let recording_new = murray(&server.base_url(), open(&path, CassetteMode::RecordNew));
+  recording_new
+    .prices
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap();
+  recording_new.prices.get_health().await.unwrap();
+
+  // assert
+  ticker.assert_hits_async(1).await;
+  health.assert_hits_async(1).await;
+  let replaying = murray(OFFLINE, open(&path, CassetteMode::Replay));
+  assert!(replaying
+    .prices
+    .get_ticker(get_ticker_params())
+    .await
+    .is_ok());
+  assert!(replaying.prices.get_health().await.is_ok());
+}
+
+#[tokio::test]
+async fn cassette_should_scrub_values_from_the_file() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/address/{}", ADDRESS));
+      then
+        .status(200)
+        .header("x-session", "some-token")
+        .body(format!(r#"{{"data": {}}}"#, address_details()));
+    })
+    .await;
+  let dir = tempfile::tempdir().unwrap();
+  let path = dir.path().join("murray.json");
+  let scrubbed = |mode| {
+    open(&path, mode)
+      .scrub(ADDRESS, "<ADDRESS>")
+      .scrub("some-token", "<TOKEN>")
+  };
+  let params = || GetAddressParams {
+    address: ADDRESS.to_string(),
+  };
+
+  let recording = murray(&server.base_url(), scrubbed(CassetteMode::Record));
+  recording
+    .blockchain
+    .get_address_details(params())
+    .await
+    .unwrap();
+
+  // act
+  let replaying = murray(OFFLINE, scrubbed(CassetteMode::Replay));
+  let details = replaying.blockchain.get_address_details(params()).await;
+
+  // assert
+  let cassette = fs::read_to_string(&path).unwrap();
+  assert!(!cassette.contains(ADDRESS));
+  assert!(!cassette.contains("some-token"));
+  assert!(cassette.contains("/address/<ADDRESS>"));
+  assert!(cassette.contains("<TOKEN>"));
+  assert_eq!(details.unwrap().address_details.address, ADDRESS);
+}
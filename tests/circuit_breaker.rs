@@ -0,0 +1,151 @@
+mod common;
+
+use std::time::Duration;
+
+use httpmock::{prelude::*, Mock};
+use murray_rs::{
+  CircuitBreakerConfig, CircuitState, ErrorKind, GetNodeDetailsParams, Murray, RetryPolicy, Service,
+};
+
+use common::body_from;
+
+async fn mock_node_details(server: &MockServer, status: u16) -> Mock<'_> {
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/node/some-public-key");
+      then
+        .status(status)
+        .body(body_from("tests/mocks/lightning/node-details.json"));
+    })
+    .await
+}
+
+async fn mock_health(server: &MockServer, status: u16) -> Mock<'_> {
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health");
+      then
+        .status(status)
+        .body(body_from("tests/mocks/lightning/get-health.json"));
+    })
+    .await
+}
+
+fn murray(server: &MockServer) -> Murray {
+  Murray::builder()
+    .lightning_endpoint(server.base_url())
+    .retry_policy(RetryPolicy::none())
+    .circuit_breaker(
+      Service::Lightning,
+      CircuitBreakerConfig::default()
+        .failure_threshold(2)
+        .open_duration(Duration::from_millis(100)),
+    )
+    .build()
+    .unwrap()
+}
+
+async fn get_node_details(murray: &Murray) -> murray_rs::Result<murray_rs::node_details::NodeData> {
+  murray
+    .lightning
+    .get_node_details(GetNodeDetailsParams {
+      public_key: "some-public-key".to_string(),
+    })
+    .await
+}
+
+#[tokio::test]
+async fn circuit_breaker_should_fail_fast_once_open() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = mock_node_details(&server, 503).await;
+  let murray = murray(&server);
+
+  // act
+  let first = get_node_details(&murray).await.unwrap_err();
+  let second = get_node_details(&murray).await.unwrap_err();
+  let third = get_node_details(&murray).await.unwrap_err();
+
+  // assert
+  mock.assert_hits_async(2).await;
+  assert_eq!(first.kind(), ErrorKind::API);
+  assert_eq!(second.kind(), ErrorKind::API);
+  assert_eq!(third.kind(), ErrorKind::CircuitOpen);
+  assert!(third.is_circuit_open());
+  assert!(third.retry_after().unwrap() > Duration::ZERO);
+  assert_eq!(murray.lightning.circuit_state(), Some(CircuitState::Open));
+}
+
+#[tokio::test]
+async fn circuit_breaker_should_close_when_health_probe_succeeds() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let down = mock_node_details(&server, 503).await;
+  mock_health(&server, 200).await;
+  let murray = murray(&server);
+
+  // act
+  for _ in 0..2 {
+    let _ = get_node_details(&murray).await;
+  }
+  down.delete_async().await;
+  let up = mock_node_details(&server, 200).await;
+  tokio::time::sleep(Duration::from_millis(150)).await;
+  let response = get_node_details(&murray).await;
+
+  // assert
+  assert!(response.is_ok());
+  up.assert_async().await;
+  assert_eq!(murray.lightning.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[tokio::test]
+async fn circuit_breaker_should_reopen_when_health_probe_fails() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = mock_node_details(&server, 503).await;
+  let health = mock_health(&server, 503).await;
+  let murray = murray(&server);
+
+  // act
+  for _ in 0..2 {
+    let _ = get_node_details(&murray).await;
+  }
+  tokio::time::sleep(Duration::from_millis(150)).await;
+  let error = get_node_details(&murray).await.unwrap_err();
+
+  // assert
+  assert!(error.is_circuit_open());
+  health.assert_async().await;
+  mock.assert_hits_async(2).await;
+  assert_eq!(murray.lightning.circuit_state(), Some(CircuitState::Open));
+}
+
+#[tokio::test]
+async fn circuit_breaker_should_ignore_client_errors() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = mock_node_details(&server, 404).await;
+  let murray = murray(&server);
+
+  // act
+  for _ in 0..3 {
+    let _ = get_node_details(&murray).await;
+  }
+
+  // assert
+  mock.assert_hits_async(3).await;
+  assert_eq!(murray.lightning.circuit_state(), Some(CircuitState::Closed));
+}
+
+#[tokio::test]
+async fn circuit_breaker_should_be_off_by_default() {
+  // arrange
+  let murray = Murray::default();
+
+  // act
+  let state = murray.lightning.circuit_state();
+
+  // assert
+  assert_eq!(state, None);
+}
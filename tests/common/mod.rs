@@ -0,0 +1,42 @@
+//! Fixtures shared by the integration tests, each of
+//! which only uses some of them.
+#![allow(dead_code)]
+
+use std::fs;
+
+use murray_rs::{GetTickerParams, Murray, RetryPolicy, Symbol};
+use serde_json::Value;
+
+pub const TXID: &str = "15e10745f15593a899cef391191bdd3d7c12412cc4696b7bcb669d0feadc8521";
+
+pub fn read_mock(path: &str) -> Value {
+  let expected_response = fs::read_to_string(path).expect("Unable to read file");
+  serde_json::from_str(&expected_response).expect("Unable to parse")
+}
+
+/// The mock at `path` as sent by the services, i.e. under `data`.
+pub fn body_from(path: &str) -> String {
+  let expected_response = fs::read_to_string(path).expect("Unable to read file");
+  format!(r#"{{"data":  {}}}"#, expected_response)
+}
+
+pub fn ticker_body() -> String {
+  body_from("tests/mocks/prices/get-ticker.json")
+}
+
+pub fn get_ticker_params() -> GetTickerParams {
+  GetTickerParams {
+    symbol: Symbol::BTCUSD,
+  }
+}
+
+/// Sends the calls of every service to `url`, without retrying them.
+pub fn murray(url: &str) -> Murray {
+  Murray::builder()
+    .blockchain_endpoint(url)
+    .lightning_endpoint(url)
+    .prices_endpoint(url)
+    .retry_policy(RetryPolicy::none())
+    .build()
+    .unwrap()
+}
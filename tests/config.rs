@@ -0,0 +1,254 @@
+mod common;
+
+use std::{env, sync::Mutex};
+
+use httpmock::prelude::*;
+use murray_rs::{ConfigError, Murray};
+
+use common::{get_ticker_params, ticker_body};
+
+/// Tests setting environment variables must not run at the same time.
+static ENV: Mutex<()> = Mutex::new(());
+
+/// Runs `test` with the environment variables `vars` set.
+fn with_env<T>(vars: &[(&str, &str)], test: impl FnOnce() -> T) -> T {
+  let _guard = ENV.lock().unwrap_or_else(|e| e.into_inner());
+  for (name, value) in vars {
+    env::set_var(name, value);
+  }
+  let result = test();
+  for (name, _) in vars {
+    env::remove_var(name);
+  }
+  result
+}
+
+async fn mock_ticker_with_header<'a>(
+  server: &'a MockServer,
+  name: &str,
+  value: &str,
+) -> httpmock::Mock<'a> {
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker").header(name, value);
+      then.status(200).body(ticker_body());
+    })
+    .await
+}
+
+#[tokio::test]
+async fn from_env_should_configure_endpoints_and_auth() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = mock_ticker_with_header(&server, "Authorization", "Bearer some-token").await;
+  let url = server.base_url();
+  let murray = with_env(
+    &[
+      ("MURRAY_PRICES_URL", &url),
+      ("MURRAY_PRICES_BEARER_TOKEN", "some-token"),
+      ("MURRAY_TIMEOUT", "5s"),
+      ("MURRAY_RETRY_MAX_ATTEMPTS", "1"),
+    ],
+    Murray::from_env,
+  )
+  .unwrap();
+
+  // act
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  mock.assert_async().await;
+  assert!(response.is_ok());
+}
+
+#[test]
+fn from_env_should_name_the_invalid_variable() {
+  // arrange
+  let invalid = [
+    ("MURRAY_TIMEOUT", "soon"),
+    ("MURRAY_RETRY_MAX_ATTEMPTS", "-1"),
+    ("MURRAY_BLOCKCHAIN_URL", "blockchain.example.com"),
+    (
+      "MURRAY_LIGHTNING_FALLBACK_URLS",
+      "http://ok.example.com,ftp://ko.example.com",
+    ),
+    ("MURRAY_PRICES_API_KEY_HEADER", "not a header"),
+  ];
+
+  for (name, value) in invalid {
+    // act
+    let error = with_env(
+      &[(name, value), ("MURRAY_PRICES_API_KEY", "some-key")],
+      Murray::from_env,
+    )
+    .err()
+    .unwrap();
+
+    // assert
+    assert!(
+      matches!(&error, ConfigError::Invalid { key, .. } if key == name),
+      "{}",
+      error
+    );
+  }
+}
+
+#[test]
+fn from_env_should_reject_conflicting_credentials() {
+  // act
+  let error = with_env(
+    &[
+      ("MURRAY_PRICES_BEARER_TOKEN", "some-token"),
+      ("MURRAY_PRICES_API_KEY", "some-key"),
+    ],
+    Murray::from_env,
+  )
+  .err()
+  .unwrap();
+
+  // assert
+  assert_eq!(error.key(), Some("MURRAY_PRICES_API_KEY"));
+  assert!(!error.to_string().contains("some-key"));
+}
+
+#[test]
+fn from_env_should_reject_an_api_key_header_without_api_key() {
+  // act
+  let error = with_env(
+    &[("MURRAY_LIGHTNING_API_KEY_HEADER", "x-token")],
+    Murray::from_env,
+  )
+  .err()
+  .unwrap();
+
+  // assert
+  assert_eq!(error.key(), Some("MURRAY_LIGHTNING_API_KEY_HEADER"));
+}
+
+#[cfg(feature = "config-file")]
+mod config_file {
+  use std::fs;
+
+  use super::*;
+
+  fn write(name: &str, contents: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(name);
+    fs::write(&path, contents).unwrap();
+    (dir, path)
+  }
+
+  #[tokio::test]
+  async fn from_config_file_should_read_toml() {
+    // arrange
+    let server = MockServer::start_async().await;
+    let mock = mock_ticker_with_header(&server, "x-api-key", "some-key").await;
+    let (_dir, path) = write(
+      "murray.toml",
+      &format!(
+        r#"
+timeout = "5s"
+connect_timeout = 2
+
+[retry]
+max_attempts = 1
+
+[prices]
+url = "{}"
+auth = {{ api_key = "some-key" }}
+"#,
+        server.base_url()
+      ),
+    );
+
+    // act
+    let murray = Murray::from_config_file(&path).unwrap();
+    let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+    // assert
+    mock.assert_async().await;
+    assert!(response.is_ok());
+  }
+
+  #[tokio::test]
+  async fn from_config_file_should_read_yaml() {
+    // arrange
+    let server = MockServer::start_async().await;
+    let mock = mock_ticker_with_header(&server, "x-token", "some-key").await;
+    let (_dir, path) = write(
+      "murray.yml",
+      &format!(
+        r#"
+timeout: 1.5
+retry:
+  initial_backoff: 100ms
+prices:
+  url: {}
+  auth:
+    api_key: some-key
+    api_key_header: x-token
+"#,
+        server.base_url()
+      ),
+    );
+
+    // act
+    let murray = Murray::from_config_file(&path).unwrap();
+    let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+    // assert
+    mock.assert_async().await;
+    assert!(response.is_ok());
+  }
+
+  #[test]
+  fn from_config_file_should_name_the_invalid_key() {
+    // arrange
+    let invalid = [
+      ("retry.max_backoff", "[retry]\nmax_backoff = \"later\""),
+      (
+        "blockchain.fallback_urls[1]",
+        "[blockchain]\nfallback_urls = [\"http://ok.example.com\", \"ko\"]",
+      ),
+      (
+        "lightning.auth.api_key",
+        "[lightning.auth]\nbearer_token = \"t\"\napi_key = \"k\"",
+      ),
+      (
+        "blockchain.auth.api_key_header",
+        "[blockchain.auth]\nbearer_token = \"t\"\napi_key_header = \"x-token\"",
+      ),
+      (
+        "prices.uri",
+        "[prices]\nuri = \"http://prices.example.com\"",
+      ),
+    ];
+
+    for (key, contents) in invalid {
+      let (_dir, path) = write("murray.toml", contents);
+
+      // act
+      let error = Murray::from_config_file(&path).err().unwrap();
+
+      // assert
+      assert_eq!(error.key(), Some(key), "{}", error);
+    }
+  }
+
+  #[test]
+  fn from_config_file_should_reject_unknown_formats_and_syntax_errors() {
+    // arrange
+    let (_json_dir, json) = write("murray.json", "{}");
+    let (_toml_dir, toml) = write("murray.toml", "timeout = ");
+
+    // act
+    let unsupported = Murray::from_config_file(&json).err().unwrap();
+    let syntax = Murray::from_config_file(&toml).err().unwrap();
+    let missing = Murray::from_config_file("missing.toml").err().unwrap();
+
+    // assert
+    assert!(matches!(unsupported, ConfigError::UnsupportedFormat(_)));
+    assert!(matches!(syntax, ConfigError::Parse { .. }), "{}", syntax);
+    assert!(matches!(missing, ConfigError::Read { .. }));
+  }
+}
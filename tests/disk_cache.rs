@@ -0,0 +1,246 @@
+#![cfg(feature = "disk-cache")]
+
+mod common;
+
+use std::fs;
+
+use httpmock::prelude::*;
+use murray_rs::{
+  BlockHash, BlockRef, DiskCacheConfig, GetTransactionParams, Murray, RequestOptions, Txid,
+};
+use serde_json::Value;
+
+use common::read_mock;
+
+fn body(data: &Value) -> String {
+  format!(r#"{{"data":  {}}}"#, data)
+}
+
+/// Block at `height`, which is also the tip when
+/// requested without hash nor height.
+async fn mock_tip(server: &MockServer, height: u32) -> httpmock::Mock<'_> {
+  let mut tip = read_mock("tests/mocks/blockchain/block-response.json");
+  tip["height"] = height.into();
+  server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/block")
+        .matches(|req| req.query_params.as_ref().is_none_or(|q| q.is_empty()));
+      then.status(200).body(body(&tip));
+    })
+    .await
+}
+
+fn murray(server: &MockServer, dir: &std::path::Path) -> Murray {
+  Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .disk_cache(DiskCacheConfig::new(dir).min_confirmations(6))
+    .build()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn disk_cache_should_serve_deep_transactions_across_instances() {
+  // arrange
+  let dir = tempfile::tempdir().unwrap();
+  let server = MockServer::start_async().await;
+  mock_tip(&server, 363_400).await;
+  let transaction = read_mock("tests/mocks/blockchain/get-transaction.json");
+  let txid: Txid = transaction["txid"].as_str().unwrap().parse().unwrap();
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}", txid));
+      then.status(200).body(body(&transaction));
+    })
+    .await;
+
+  // act
+  let first = murray(&server, dir.path())
+    .blockchain
+    .get_transaction(GetTransactionParams { txid })
+    .await
+    .unwrap();
+  let second = murray(&server, dir.path())
+    .blockchain
+    .get_transaction(GetTransactionParams { txid })
+    .await
+    .unwrap();
+
+  // assert
+  mock.assert_hits_async(1).await;
+  assert_eq!(first.transaction.txid, second.transaction.txid);
+  assert_eq!(
+    second.transaction.status.block_height,
+    first.transaction.status.block_height
+  );
+}
+
+#[tokio::test]
+async fn disk_cache_should_store_but_not_serve_bypassing_calls() {
+  // arrange
+  let dir = tempfile::tempdir().unwrap();
+  let server = MockServer::start_async().await;
+  mock_tip(&server, 363_400).await;
+  let transaction = read_mock("tests/mocks/blockchain/get-transaction.json");
+  let txid: Txid = transaction["txid"].as_str().unwrap().parse().unwrap();
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}", txid));
+      then.status(200).body(body(&transaction));
+    })
+    .await;
+  let murray = murray(&server, dir.path());
+  let fresh = murray
+    .blockchain
+    .with_options(RequestOptions::default().bypass_cache());
+
+  // act
+  for _ in 0..2 {
+    fresh
+      .get_transaction(GetTransactionParams { txid })
+      .await
+      .unwrap();
+  }
+  let stored = murray
+    .blockchain
+    .get_transaction(GetTransactionParams { txid })
+    .await
+    .unwrap();
+
+  // assert
+  mock.assert_hits_async(2).await;
+  assert_eq!(stored.transaction.txid, txid);
+}
+
+#[tokio::test]
+async fn disk_cache_should_not_store_transactions_with_few_confirmations() {
+  // arrange
+  let dir = tempfile::tempdir().unwrap();
+  let server = MockServer::start_async().await;
+  // the transaction is in block 363348, so it only has 3 confirmations
+  mock_tip(&server, 363_350).await;
+  let transaction = read_mock("tests/mocks/blockchain/get-transaction.json");
+  let txid: Txid = transaction["txid"].as_str().unwrap().parse().unwrap();
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}", txid));
+      then.status(200).body(body(&transaction));
+    })
+    .await;
+  let murray = murray(&server, dir.path());
+
+  // act
+  for _ in 0..2 {
+    murray
+      .blockchain
+      .get_transaction(GetTransactionParams { txid })
+      .await
+      .unwrap();
+  }
+
+  // assert
+  mock.assert_hits_async(2).await;
+}
+
+#[tokio::test]
+async fn disk_cache_should_serve_deep_blocks_by_hash_and_height() {
+  // arrange
+  let dir = tempfile::tempdir().unwrap();
+  let server = MockServer::start_async().await;
+  let block = read_mock("tests/mocks/blockchain/block-response.json");
+  let hash: BlockHash = block["id"].as_str().unwrap().parse().unwrap();
+  let height = block["height"].as_u64().unwrap() as u32;
+  mock_tip(&server, height + 10).await;
+  let mock = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/block")
+        .query_param("height", height.to_string());
+      then.status(200).body(body(&block));
+    })
+    .await;
+  let murray = murray(&server, dir.path());
+
+  // act
+  let by_height = murray
+    .blockchain
+    .get_block(BlockRef::Height(height))
+    .await
+    .unwrap();
+  let by_height_again = murray
+    .blockchain
+    .get_block(BlockRef::Height(height))
+    .await
+    .unwrap();
+  let by_hash = murray
+    .blockchain
+    .get_block(BlockRef::Hash(hash))
+    .await
+    .unwrap();
+
+  // assert
+  mock.assert_hits_async(1).await;
+  assert_eq!(by_height_again.block.id, by_height.block.id);
+  assert_eq!(by_hash.block.id, hash);
+  assert!(dir
+    .path()
+    .join("blocks")
+    .join(format!("{}.json", hash))
+    .exists());
+}
+
+#[tokio::test]
+async fn disk_cache_should_not_fetch_the_tip_again_to_store_it() {
+  // arrange
+  let dir = tempfile::tempdir().unwrap();
+  let server = MockServer::start_async().await;
+  let mock = mock_tip(&server, 363_400).await;
+
+  // act
+  let tip = murray(&server, dir.path())
+    .blockchain
+    .get_block(BlockRef::Tip)
+    .await
+    .unwrap();
+
+  // assert
+  mock.assert_hits_async(1).await;
+  assert_eq!(tip.block.height, 363_400);
+  assert!(!dir.path().join("blocks").exists());
+}
+
+#[tokio::test]
+async fn disk_cache_should_ignore_corrupted_files() {
+  // arrange
+  let dir = tempfile::tempdir().unwrap();
+  let server = MockServer::start_async().await;
+  let transaction = read_mock("tests/mocks/blockchain/get-transaction.json");
+  let txid: Txid = transaction["txid"].as_str().unwrap().parse().unwrap();
+  fs::create_dir_all(dir.path().join("transactions")).unwrap();
+  fs::write(
+    dir
+      .path()
+      .join("transactions")
+      .join(format!("{}.json", txid)),
+    "not json",
+  )
+  .unwrap();
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}", txid));
+      then.status(200).body(body(&transaction));
+    })
+    .await;
+
+  // act
+  let response = murray(&server, dir.path())
+    .blockchain
+    .get_transaction(GetTransactionParams { txid })
+    .await;
+
+  // assert
+  mock.assert_async().await;
+  assert!(response.is_ok());
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use std::error::Error;
+
+use httpmock::prelude::*;
+use murray_rs::{
+  ErrorKind, GetNodeDetailsParams, GetTransactionParams, Murray, MurrayError, Service,
+};
+
+use common::TXID;
+
+async fn get_transaction_with(status: u16, res_body: &str) -> MurrayError {
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/tx/{}", TXID));
+      then
+        .status(status)
+        .header("content-type", "application/json")
+        .body(res_body);
+    })
+    .await;
+  let mut murray = Murray::default();
+  murray.blockchain.set_base_url(server.base_url());
+
+  murray
+    .blockchain
+    .get_transaction(GetTransactionParams {
+      txid: TXID.parse().unwrap(),
+    })
+    .await
+    .unwrap_err()
+}
+
+#[tokio::test]
+async fn error_should_carry_status_service_endpoint_and_message_when_not_found() {
+  // arrange
+  let body = r#"{"statusCode":404,"message":"Transaction not found"}"#;
+
+  // act
+  let error = get_transaction_with(404, body).await;
+
+  // assert
+  assert_eq!(error.kind(), ErrorKind::API);
+  assert_eq!(error.service(), Service::Blockchain);
+  assert_eq!(error.endpoint(), "/tx/{txid}");
+  assert_eq!(error.status().map(|s| s.as_u16()), Some(404));
+  assert_eq!(error.message(), Some("Transaction not found"));
+  assert_eq!(error.body().unwrap()["statusCode"], 404);
+  assert!(error.is_not_found());
+  assert!(!error.is_rate_limited());
+  assert!(!error.is_retryable());
+}
+
+#[tokio::test]
+async fn error_should_be_retryable_when_rate_limited() {
+  // arrange
+  let body = r#"{"message":["too many requests","slow down"]}"#;
+
+  // act
+  let error = get_transaction_with(429, body).await;
+
+  // assert
+  assert!(error.is_rate_limited());
+  assert!(error.is_retryable());
+  assert_eq!(error.message(), Some("too many requests; slow down"));
+}
+
+#[tokio::test]
+async fn error_should_be_retryable_when_bad_gateway_with_plain_text_body() {
+  // act
+  let error = get_transaction_with(502, "Bad Gateway").await;
+
+  // assert
+  assert_eq!(error.kind(), ErrorKind::API);
+  assert_eq!(error.message(), Some("Bad Gateway"));
+  assert!(error.body().is_none());
+  assert!(error.is_retryable());
+}
+
+#[tokio::test]
+async fn error_should_expose_json_source_when_body_returns_wrong_json() {
+  // act
+  let error = get_transaction_with(200, "wrong-return").await;
+
+  // assert
+  assert_eq!(error.kind(), ErrorKind::JSONParse);
+  assert!(error.status().is_none());
+  assert!(!error.is_retryable());
+  assert!(error
+    .source()
+    .unwrap()
+    .downcast_ref::<serde_json::Error>()
+    .is_some());
+}
+
+#[tokio::test]
+async fn error_should_be_a_retryable_request_error_when_service_is_unreachable() {
+  // arrange
+  let mut murray = Murray::default();
+  murray
+    .lightning
+    .set_base_url("http://127.0.0.1:1".to_string());
+
+  // act
+  let error = murray
+    .lightning
+    .get_node_details(GetNodeDetailsParams {
+      public_key: "some_key".to_string(),
+    })
+    .await
+    .unwrap_err();
+
+  // assert
+  assert_eq!(error.kind(), ErrorKind::Request);
+  assert_eq!(error.service(), Service::Lightning);
+  assert_eq!(error.endpoint(), "/node/{public_key}");
+  assert!(error.is_retryable());
+  assert!(error
+    .source()
+    .unwrap()
+    .downcast_ref::<reqwest::Error>()
+    .is_some());
+}
@@ -0,0 +1,168 @@
+mod common;
+
+use std::time::Duration;
+
+use httpmock::{prelude::*, Mock};
+use murray_rs::{
+  FailoverPolicy, FailoverStrategy, Murray, PostTransactionParams, RetryPolicy, Service,
+};
+
+use common::{get_ticker_params, ticker_body};
+
+async fn mock_ticker(server: &MockServer, status: u16, delay: Duration) -> Mock<'_> {
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(status).delay(delay).body(ticker_body());
+    })
+    .await
+}
+
+fn murray(primary: &MockServer, fallback: &MockServer, policy: FailoverPolicy) -> Murray {
+  Murray::builder()
+    .prices_endpoint(primary.base_url())
+    .fallback_endpoints(Service::Prices, [fallback.base_url()])
+    .failover_policy(policy)
+    .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(10)))
+    .build()
+    .unwrap()
+}
+
+async fn get_ticker(murray: &Murray) -> murray_rs::Result<murray_rs::GetTickerReturn> {
+  murray.prices.get_ticker(get_ticker_params()).await
+}
+
+#[tokio::test]
+async fn failover_should_switch_to_fallback_when_primary_is_down() {
+  // arrange
+  let primary = MockServer::start_async().await;
+  let fallback = MockServer::start_async().await;
+  let primary_mock = mock_ticker(&primary, 503, Duration::ZERO).await;
+  let fallback_mock = mock_ticker(&fallback, 200, Duration::ZERO).await;
+  let murray = murray(&primary, &fallback, FailoverPolicy::default());
+
+  // act
+  let first = get_ticker(&murray).await;
+  let second = get_ticker(&murray).await;
+
+  // assert
+  assert!(first.is_ok() && second.is_ok());
+  // once marked unhealthy, the primary isn't tried again
+  primary_mock.assert_hits_async(1).await;
+  fallback_mock.assert_hits_async(2).await;
+}
+
+#[tokio::test]
+async fn failover_should_fail_back_once_primary_is_healthy_again() {
+  // arrange
+  let primary = MockServer::start_async().await;
+  let fallback = MockServer::start_async().await;
+  let down = mock_ticker(&primary, 503, Duration::ZERO).await;
+  primary
+    .mock_async(|when, then| {
+      when.method(GET).path("/health");
+      then.status(200);
+    })
+    .await;
+  mock_ticker(&fallback, 200, Duration::ZERO).await;
+  let murray = murray(
+    &primary,
+    &fallback,
+    FailoverPolicy::default().probe_interval(Duration::from_millis(50)),
+  );
+
+  // act
+  get_ticker(&murray).await.unwrap();
+  down.delete_async().await;
+  let up = mock_ticker(&primary, 200, Duration::ZERO).await;
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  // still served by the fallback, but starts probing the primary
+  get_ticker(&murray).await.unwrap();
+  tokio::time::sleep(Duration::from_millis(100)).await;
+  get_ticker(&murray).await.unwrap();
+
+  // assert
+  up.assert_hits_async(1).await;
+}
+
+#[tokio::test]
+async fn failover_should_take_turns_with_round_robin() {
+  // arrange
+  let primary = MockServer::start_async().await;
+  let fallback = MockServer::start_async().await;
+  let primary_mock = mock_ticker(&primary, 200, Duration::ZERO).await;
+  let fallback_mock = mock_ticker(&fallback, 200, Duration::ZERO).await;
+  let murray = murray(
+    &primary,
+    &fallback,
+    FailoverPolicy::default().strategy(FailoverStrategy::RoundRobin),
+  );
+
+  // act
+  for _ in 0..4 {
+    get_ticker(&murray).await.unwrap();
+  }
+
+  // assert
+  primary_mock.assert_hits_async(2).await;
+  fallback_mock.assert_hits_async(2).await;
+}
+
+#[tokio::test]
+async fn failover_should_prefer_the_fastest_endpoint_with_lowest_latency() {
+  // arrange
+  let primary = MockServer::start_async().await;
+  let fallback = MockServer::start_async().await;
+  let primary_mock = mock_ticker(&primary, 200, Duration::from_millis(200)).await;
+  let fallback_mock = mock_ticker(&fallback, 200, Duration::ZERO).await;
+  let murray = murray(
+    &primary,
+    &fallback,
+    FailoverPolicy::default().strategy(FailoverStrategy::LowestLatency),
+  );
+
+  // act
+  for _ in 0..4 {
+    get_ticker(&murray).await.unwrap();
+  }
+
+  // assert
+  primary_mock.assert_hits_async(1).await;
+  fallback_mock.assert_hits_async(3).await;
+}
+
+#[tokio::test]
+async fn failover_should_not_resend_post_transaction_to_fallback() {
+  // arrange
+  let primary = MockServer::start_async().await;
+  let fallback = MockServer::start_async().await;
+  primary
+    .mock_async(|when, then| {
+      when.method(POST).path("/tx");
+      then.status(503);
+    })
+    .await;
+  let fallback_mock = fallback
+    .mock_async(|when, then| {
+      when.method(POST).path("/tx");
+      then.status(200);
+    })
+    .await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(primary.base_url())
+    .fallback_endpoints(Service::Blockchain, [fallback.base_url()])
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray
+    .blockchain
+    .post_transaction(PostTransactionParams {
+      tx_hex: "some-tx-hex".to_string(),
+    })
+    .await;
+
+  // assert
+  assert!(response.is_err());
+  fallback_mock.assert_hits_async(0).await;
+}
@@ -0,0 +1,134 @@
+#![cfg(feature = "test-utils")]
+
+mod common;
+
+use std::sync::{
+  atomic::{AtomicUsize, Ordering},
+  Arc,
+};
+
+use httpmock::prelude::*;
+use murray_rs::{
+  fake::{FakeBlockchain, FakeLightning, FakePrices},
+  reqwest::StatusCode,
+  BlockchainApi, ErrorKind, GetHealthResponse, GetTickerParams, GetTickerReturn, LightningApi,
+  Murray, MurrayError, PostTransactionParams, PostTransactionResponse, PricesApi, Service, Symbol,
+};
+
+use common::{read_mock, ticker_body, TXID};
+
+fn ticker() -> GetTickerReturn {
+  serde_json::from_value(read_mock("tests/mocks/prices/get-ticker.json")).unwrap()
+}
+
+fn get_ticker_params() -> GetTickerParams {
+  GetTickerParams {
+    symbol: Symbol::BTCBRL,
+  }
+}
+
+/// Some downstream code depending on the service traits.
+async fn describe(prices: &dyn PricesApi, lightning: &dyn LightningApi) -> String {
+  let ticker = prices.get_ticker(get_ticker_params()).await.unwrap();
+  let health = lightning.get_health().await.unwrap();
+  format!("{} {} ({})", ticker.symbol, ticker.price, health.message)
+}
+
+#[tokio::test]
+async fn fakes_should_answer_with_programmed_responses() {
+  // arrange
+  let prices = FakePrices::new().on_get_ticker(|params| {
+    assert_eq!(params.symbol, Symbol::BTCBRL);
+    Ok(ticker())
+  });
+  let lightning = FakeLightning::new().on_get_health(|| {
+    Ok(GetHealthResponse {
+      message: "OK".to_string(),
+    })
+  });
+
+  // act
+  let description = describe(&prices, &lightning).await;
+
+  // assert
+  assert_eq!(description, "BTCBRL 339225.00000000 (OK)");
+}
+
+#[tokio::test]
+async fn fakes_should_be_programmable_per_call() {
+  // arrange
+  let calls = Arc::new(AtomicUsize::new(0));
+  let blockchain: Arc<dyn BlockchainApi> = Arc::new(FakeBlockchain::new().on_post_transaction({
+    let calls = calls.clone();
+    move |_| match calls.fetch_add(1, Ordering::SeqCst) {
+      0 => Err(MurrayError::fake_api(
+        Service::Blockchain,
+        "/tx",
+        StatusCode::BAD_REQUEST,
+        r#"{"message": "bad-txns-inputs-missingorspent"}"#,
+      )),
+      _ => Ok(PostTransactionResponse {
+        txid: TXID.parse().unwrap(),
+        ..Default::default()
+      }),
+    }
+  }));
+  let params = || PostTransactionParams {
+    tx_hex: "0100".to_string(),
+  };
+
+  // act
+  let first = blockchain.post_transaction(params()).await.unwrap_err();
+  let second = blockchain.post_transaction(params()).await.unwrap();
+
+  // assert
+  assert_eq!(first.kind(), ErrorKind::API);
+  assert_eq!(first.status(), Some(StatusCode::BAD_REQUEST));
+  assert_eq!(first.message(), Some("bad-txns-inputs-missingorspent"));
+  assert_eq!(second.txid.to_string(), TXID);
+}
+
+#[tokio::test]
+#[should_panic(expected = "FakeBlockchain::get_mempool was called without a response")]
+async fn fakes_should_panic_without_programmed_response() {
+  // arrange
+  let blockchain = FakeBlockchain::new().on_get_hashrate(|| {
+    Err(MurrayError::fake(
+      ErrorKind::Request,
+      Service::Blockchain,
+      "/hashrate",
+    ))
+  });
+
+  // act
+  let _ = blockchain.get_mempool().await;
+}
+
+#[tokio::test]
+async fn services_should_implement_the_traits() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health");
+      then.status(200).body(r#"{"data": {"message": "OK"}}"#);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .lightning_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  let description = describe(&murray.prices, &murray.lightning).await;
+
+  // assert
+  assert_eq!(description, "BTCBRL 339225.00000000 (OK)");
+}
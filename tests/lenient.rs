@@ -0,0 +1,114 @@
+mod common;
+
+use httpmock::prelude::*;
+use murray_rs::{ErrorKind, GetAddressParams};
+use serde_json::json;
+
+use common::{murray, read_mock};
+
+const ADDRESS: &str = "bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh";
+
+fn address_params() -> GetAddressParams {
+  GetAddressParams {
+    address: ADDRESS.to_string(),
+  }
+}
+
+#[tokio::test]
+async fn get_address_utxos_lenient_should_return_the_good_items_and_the_errors() {
+  // arrange
+  let utxo = read_mock("tests/mocks/blockchain/get-address-utxos.json")[0].clone();
+  let malformed = json!({"txid": "aa", "vout": -1, "status": {"confirmed": false}, "value": 1});
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path(format!("/address/{}/txs/utxo", ADDRESS));
+      then
+        .status(200)
+        .body(json!({"data": [utxo, malformed, utxo]}).to_string());
+    })
+    .await;
+  let murray = murray(&server.base_url());
+
+  // act
+  let strict = murray
+    .blockchain
+    .get_address_utxos(address_params())
+    .await
+    .unwrap_err();
+  let lenient = murray
+    .blockchain
+    .get_address_utxos_lenient(address_params())
+    .await
+    .unwrap();
+
+  // assert
+  assert_eq!(strict.kind(), ErrorKind::JSONParse);
+  assert!(!lenient.is_complete());
+  assert_eq!(lenient.items.len(), 2);
+  assert_eq!(lenient.items[1].txid.to_string(), utxo["txid"]);
+  assert_eq!(lenient.errors.len(), 1);
+  assert_eq!(lenient.errors[0].index, 1);
+  assert_eq!(lenient.errors[0].raw, malformed);
+  assert!(lenient.errors[0]
+    .to_string()
+    .starts_with("item 1 could not be decoded"));
+}
+
+#[tokio::test]
+async fn get_address_transactions_lenient_should_decode_every_good_item() {
+  // arrange
+  let transactions = read_mock("tests/mocks/blockchain/get-address-txs.json");
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path(format!("/address/{}/txs", ADDRESS));
+      then
+        .status(200)
+        .body(json!({ "data": transactions }).to_string());
+    })
+    .await;
+
+  // act
+  let response = murray(&server.base_url())
+    .blockchain
+    .with_meta()
+    .get_address_transactions_lenient(address_params())
+    .await
+    .unwrap();
+
+  // assert
+  assert_eq!(response.status(), 200);
+  let partial = response.into_data();
+  assert!(partial.is_complete());
+  assert_eq!(
+    partial.items[0].address_transactions.txid.to_string(),
+    transactions[0]["txid"]
+  );
+}
+
+#[tokio::test]
+async fn get_fees_mempool_blocks_lenient_should_fail_when_the_response_is_not_a_list() {
+  // arrange
+  let block = read_mock("tests/mocks/blockchain/fees-mempool-blocks.json")[0].clone();
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/fees/mempool-blocks");
+      then.status(200).body(json!({ "data": block }).to_string());
+    })
+    .await;
+
+  // act
+  let error = murray(&server.base_url())
+    .blockchain
+    .get_fees_mempool_blocks_lenient()
+    .await
+    .unwrap_err();
+
+  // assert
+  assert_eq!(error.kind(), ErrorKind::JSONParse);
+  assert_eq!(error.endpoint(), "/fees/mempool-blocks");
+}
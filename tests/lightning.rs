@@ -9,19 +9,19 @@ struct Sut {
 }
 
 impl Sut {
-  fn new() -> Self {
-    let server = MockServer::start();
+  async fn new() -> Self {
+    let server = MockServer::start_async().await;
     Sut { server }
   }
 
-  fn from(
+  async fn from(
     &self,
     path: &str,
     status: u16,
     method: Method,
     req_body: &str,
     res_body: &str,
-  ) -> (Mock, Murray) {
+  ) -> (Mock<'_>, Murray) {
     // Create a mock on the server.
     let mock = match method {
       Method::GET
@@ -29,20 +29,30 @@ impl Sut {
       | Method::DELETE
       | Method::OPTIONS
       | Method::TRACE
-      | Method::CONNECT => self.server.mock(|when, then| {
-        when.method(method).path(path);
-        then
-          .status(status)
-          .header("content-type", "application/json")
-          .body(res_body);
-      }),
-      Method::POST | Method::PATCH | Method::PUT => self.server.mock(|when, then| {
-        when.method(method).path(path).body(req_body);
-        then
-          .status(status)
-          .header("content-type", "application/json")
-          .body(res_body);
-      }),
+      | Method::CONNECT => {
+        self
+          .server
+          .mock_async(|when, then| {
+            when.method(method).path(path);
+            then
+              .status(status)
+              .header("content-type", "application/json")
+              .body(res_body);
+          })
+          .await
+      }
+      Method::POST | Method::PATCH | Method::PUT => {
+        self
+          .server
+          .mock_async(|when, then| {
+            when.method(method).path(path).body(req_body);
+            then
+              .status(status)
+              .header("content-type", "application/json")
+              .body(res_body);
+          })
+          .await
+      }
     };
 
     let mut murray = Murray::default();
@@ -53,22 +63,24 @@ impl Sut {
 }
 
 /// GET NODE DETAILS
-#[test]
-fn get_node_details_should_return_successfully() {
+#[tokio::test]
+async fn get_node_details_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/lightning/node-details.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
   let pub_key_param = String::from("some-pubkey");
-  let sut = Sut::new();
-  let (mock, murray) = sut.from(
-    format!("/node/{}", pub_key_param).as_ref(),
-    200,
-    Method::GET,
-    "",
-    &body,
-  );
+  let sut = Sut::new().await;
+  let (mock, murray) = sut
+    .from(
+      format!("/node/{}", pub_key_param).as_ref(),
+      200,
+      Method::GET,
+      "",
+      &body,
+    )
+    .await;
 
   // act
   let response = murray
@@ -76,27 +88,72 @@ fn get_node_details_should_return_successfully() {
     .get_node_details(GetNodeDetailsParams {
       public_key: pub_key_param,
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.capacity, expected_response["capacity"]);
 }
 
-#[test]
+#[tokio::test]
+async fn get_node_details_should_decode_nodes_without_location() {
+  // arrange
+  let expected_response =
+    fs::read_to_string("tests/mocks/lightning/node-details.json").expect("Unable to read file");
+  let mut expected_response: Value =
+    serde_json::from_str(&expected_response).expect("Unable to parse");
+  for field in [
+    "as_number",
+    "country_id",
+    "longitude",
+    "latitude",
+    "iso_code",
+    "as_organization",
+    "country",
+  ] {
+    expected_response[field] = Value::Null;
+  }
+  expected_response["city"] = serde_json::json!({"en": "Ashburn"});
+  expected_response["channels"][0]["closing_reason"] = 2.into();
+  let body = format!(r#"{{"data":  {}}}"#, expected_response);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from("/node/tor-node", 200, Method::GET, "", &body)
+    .await;
+
+  // act
+  let response = murray
+    .lightning
+    .get_node_details(GetNodeDetailsParams {
+      public_key: "tor-node".to_string(),
+    })
+    .await
+    .unwrap();
+
+  // assert
+  assert!(response.country.is_none());
+  assert!(response.iso_code.is_none());
+  assert_eq!(response.city.unwrap()["en"], "Ashburn");
+  assert_eq!(response.channels[0].closing_reason, Some(2));
+}
+
+#[tokio::test]
 #[should_panic]
-fn get_node_details_should_return_error_when_problem_with_server() {
+async fn get_node_details_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
   let pub_key_param = String::from("some-pubkey");
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from(
-    format!("/node/{}", pub_key_param).as_ref(),
-    400,
-    Method::GET,
-    "",
-    &body,
-  );
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut
+    .from(
+      format!("/node/{}", pub_key_param).as_ref(),
+      400,
+      Method::GET,
+      "",
+      &body,
+    )
+    .await;
 
   // act
   let _response = murray
@@ -104,135 +161,138 @@ fn get_node_details_should_return_error_when_problem_with_server() {
     .get_node_details(GetNodeDetailsParams {
       public_key: pub_key_param,
     })
+    .await
     .unwrap();
 }
 
 /// GET HEALTH
-#[test]
-fn get_health_should_return_successfully() {
+#[tokio::test]
+async fn get_health_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/lightning/get-health.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/health", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/health", 200, Method::GET, "", &body).await;
 
   // act
-  let response = murray.lightning.get_health().unwrap();
+  let response = murray.lightning.get_health().await.unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.message, expected_response["message"]);
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_health_should_return_error_when_problem_with_server() {
+async fn get_health_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/health", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/health", 400, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.lightning.get_health().unwrap();
+  let _response = murray.lightning.get_health().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_health_should_return_error_when_body_returns_wrong_json() {
+async fn get_health_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/health", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/health", 200, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.lightning.get_health().unwrap();
+  let _response = murray.lightning.get_health().await.unwrap();
 }
 
 /// GET STATISTICS
-#[test]
-fn get_statistics_should_return_successfully() {
+#[tokio::test]
+async fn get_statistics_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/lightning/statistics.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/statistics", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/statistics", 200, Method::GET, "", &body).await;
 
   // act
-  let response = murray.lightning.get_statistics().unwrap();
+  let response = murray.lightning.get_statistics().await.unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.latest.id, expected_response["latest"]["id"]);
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_statistics_should_return_error_when_problem_with_server() {
+async fn get_statistics_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/statistics", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/statistics", 400, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.lightning.get_statistics().unwrap();
+  let _response = murray.lightning.get_statistics().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_statistics_should_return_error_when_body_returns_wrong_json() {
+async fn get_statistics_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/statistics", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/statistics", 200, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.lightning.get_statistics().unwrap();
+  let _response = murray.lightning.get_statistics().await.unwrap();
 }
 
-
 /// GET TOP NODES
-#[test]
-fn get_top_nodes_should_return_successfully() {
+#[tokio::test]
+async fn get_top_nodes_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/lightning/top-nodes.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/top", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/top", 200, Method::GET, "", &body).await;
 
   // act
-  let response = murray.lightning.get_top_nodes().unwrap();
+  let response = murray.lightning.get_top_nodes().await.unwrap();
 
   // assert
-  mock.assert();
-  assert_eq!(response.top_by_channels[0].public_key, expected_response["topByChannels"][0]["publicKey"]);
+  mock.assert_async().await;
+  assert_eq!(
+    response.top_by_channels[0].public_key,
+    expected_response["topByChannels"][0]["publicKey"]
+  );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_top_nodes_should_return_error_when_problem_with_server() {
+async fn get_top_nodes_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/top", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/top", 400, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.lightning.get_top_nodes().unwrap();
+  let _response = murray.lightning.get_top_nodes().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_top_nodes_should_return_error_when_body_returns_wrong_json() {
+async fn get_top_nodes_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/top", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/top", 200, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.lightning.get_top_nodes().unwrap();
+  let _response = murray.lightning.get_top_nodes().await.unwrap();
 }
@@ -0,0 +1,179 @@
+mod common;
+
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use murray_rs::{CacheConfig, Murray, RateLimit, RetryPolicy, Service};
+
+use common::{get_ticker_params, ticker_body};
+
+#[tokio::test]
+async fn metrics_should_count_requests_per_endpoint_and_status() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/tickers");
+      then.status(404).body(r#"{"message": "Not found"}"#);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  for _ in 0..2 {
+    murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  }
+  let _ = murray.prices.get_tickers(get_ticker_params()).await;
+
+  // assert
+  let requests = murray.metrics().requests;
+  assert_eq!(requests.len(), 2);
+  assert_eq!(requests[0].service, Service::Prices);
+  assert_eq!(requests[0].endpoint, "/ticker");
+  assert_eq!(requests[0].status, "200");
+  assert_eq!(requests[0].count, 2);
+  assert!(requests[0].is_success());
+  assert_eq!(requests[1].endpoint, "/tickers");
+  assert_eq!(requests[1].status, "404");
+  assert_eq!(requests[1].count, 1);
+  assert!(!requests[1].is_success());
+}
+
+#[tokio::test]
+async fn metrics_should_record_every_attempt() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(503);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(
+      RetryPolicy::default()
+        .initial_backoff(Duration::from_millis(10))
+        .jitter(false),
+    )
+    .build()
+    .unwrap();
+
+  // act
+  let _ = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  let requests = murray.metrics().requests;
+  assert_eq!(requests.len(), 1);
+  assert_eq!(requests[0].status, "503");
+  assert_eq!(requests[0].count, 3);
+  let (_, slowest) = requests[0].latency.buckets.last().unwrap();
+  assert_eq!(*slowest, 3);
+}
+
+#[tokio::test]
+async fn metrics_should_not_record_cache_hits() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default())
+    .build()
+    .unwrap();
+
+  // act
+  for _ in 0..3 {
+    murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  }
+
+  // assert
+  assert_eq!(murray.metrics().requests[0].count, 1);
+}
+
+#[tokio::test]
+async fn metrics_should_record_calls_rejected_without_being_sent() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(RetryPolicy::none())
+    .rate_limit(
+      Service::Prices,
+      RateLimit::per_second(1).burst(1).fail_fast(),
+    )
+    .build()
+    .unwrap();
+
+  // act
+  for _ in 0..2 {
+    let _ = murray.prices.get_ticker(get_ticker_params()).await;
+  }
+
+  // assert
+  let statuses: Vec<String> = murray
+    .metrics()
+    .requests
+    .into_iter()
+    .map(|requests| requests.status)
+    .collect();
+  assert_eq!(statuses, ["200", "rate_limited"]);
+}
+
+#[tokio::test]
+async fn metrics_should_render_in_prometheus_text_format() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  let text = murray.metrics().to_prometheus();
+
+  // assert
+  let labels = r#"service="prices",endpoint="/ticker",status="200""#;
+  assert!(text.contains("# TYPE murray_requests_total counter\n"));
+  assert!(text.contains(&format!("murray_requests_total{{{}}} 1\n", labels)));
+  assert!(text.contains("# TYPE murray_request_duration_seconds histogram\n"));
+  assert!(text.contains(&format!(
+    "murray_request_duration_seconds_bucket{{{},le=\"10\"}} 1\n",
+    labels
+  )));
+  assert!(text.contains(&format!(
+    "murray_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 1\n",
+    labels
+  )));
+  assert!(text.contains(&format!(
+    "murray_request_duration_seconds_count{{{}}} 1\n",
+    labels
+  )));
+}
@@ -0,0 +1,141 @@
+mod common;
+
+use std::{
+  error::Error,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+  task::{Context, Poll},
+  time::Duration,
+};
+
+use httpmock::prelude::*;
+use murray_rs::{
+  reqwest::{header::HeaderValue, Request, Response},
+  tower::{layer::layer_fn, service_fn, util::MapRequestLayer, Service},
+  BoxError, ErrorKind, HttpService, Murray, RetryPolicy,
+};
+
+use common::{get_ticker_params, ticker_body};
+
+/// Counts the requests sent through it.
+#[derive(Clone)]
+struct Count<S> {
+  inner: S,
+  count: Arc<AtomicUsize>,
+}
+
+impl<S: Service<Request>> Service<Request> for Count<S> {
+  type Response = S::Response;
+  type Error = S::Error;
+  type Future = S::Future;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx)
+  }
+
+  fn call(&mut self, request: Request) -> Self::Future {
+    self.count.fetch_add(1, Ordering::SeqCst);
+    self.inner.call(request)
+  }
+}
+
+#[tokio::test]
+async fn layer_should_be_able_to_change_requests() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when
+        .method(GET)
+        .path("/ticker")
+        .header("x-api-key", "some-key");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .layer(MapRequestLayer::new(|mut request: Request| {
+      request
+        .headers_mut()
+        .insert("x-api-key", HeaderValue::from_static("some-key"));
+      request
+    }))
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  mock.assert_async().await;
+  assert!(response.is_ok());
+}
+
+#[tokio::test]
+async fn layer_should_see_every_attempt() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(503);
+    })
+    .await;
+  let count = Arc::new(AtomicUsize::new(0));
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(
+      RetryPolicy::default()
+        .initial_backoff(Duration::from_millis(10))
+        .jitter(false),
+    )
+    .layer(layer_fn({
+      let count = count.clone();
+      move |inner| Count {
+        inner,
+        count: count.clone(),
+      }
+    }))
+    .build()
+    .unwrap();
+
+  // act
+  let _ = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  assert_eq!(count.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn layer_errors_should_be_request_errors() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(RetryPolicy::none())
+    .layer(layer_fn(|_inner: HttpService| {
+      service_fn(|_request: Request| async { Err::<Response, BoxError>("chaos monkey".into()) })
+    }))
+    .build()
+    .unwrap();
+
+  // act
+  let error = murray
+    .prices
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap_err();
+
+  // assert
+  mock.assert_hits_async(0).await;
+  assert_eq!(error.kind(), ErrorKind::Request);
+  assert_eq!(error.source().unwrap().to_string(), "chaos monkey");
+}
@@ -0,0 +1,291 @@
+mod common;
+
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use murray_rs::{
+  CacheConfig, CacheStatus, CancellationToken, CircuitBreakerConfig, CircuitState, ErrorKind,
+  Murray, RequestOptions, RetryPolicy, Service,
+};
+
+use common::{get_ticker_params, ticker_body};
+
+#[tokio::test]
+async fn timeout_should_bound_the_whole_call() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(503).delay(Duration::from_millis(150));
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(10)))
+    .build()
+    .unwrap();
+  let options = RequestOptions::default().timeout(Duration::from_millis(250));
+
+  // act
+  let error = murray
+    .prices
+    .with_options(options)
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap_err();
+
+  // assert
+  mock.assert_hits_async(2).await;
+  assert_eq!(error.kind(), ErrorKind::DeadlineExceeded);
+  assert_eq!(error.endpoint(), "/ticker");
+  assert!(error.is_timeout());
+  assert!(!error.is_retryable());
+}
+
+#[tokio::test]
+async fn cancellation_token_should_cancel_the_call() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then
+        .status(200)
+        .delay(Duration::from_secs(5))
+        .body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .build()
+    .unwrap();
+  let token = CancellationToken::new();
+  let cancelled = CancellationToken::new();
+  cancelled.cancel();
+  let prices = murray
+    .prices
+    .with_options(RequestOptions::default().cancellation_token(token.clone()));
+
+  // act
+  let canceller = tokio::spawn(async move {
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    token.cancel();
+  });
+  let error = prices.get_ticker(get_ticker_params()).await.unwrap_err();
+  canceller.await.unwrap();
+  let never_sent = murray
+    .prices
+    .with_options(RequestOptions::default().cancellation_token(cancelled))
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap_err();
+
+  // assert
+  mock.assert_hits_async(1).await;
+  assert_eq!(error.kind(), ErrorKind::Cancelled);
+  assert_eq!(never_sent.kind(), ErrorKind::Cancelled);
+  let metrics = murray.metrics();
+  assert_eq!(metrics.requests[0].status, "cancelled");
+  assert_eq!(metrics.requests[0].count, 2);
+}
+
+#[tokio::test]
+async fn retry_policy_should_override_the_one_of_the_service() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(503);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(10)))
+    .build()
+    .unwrap();
+
+  // act
+  let overridden = murray
+    .prices
+    .with_options(RequestOptions::default().retry_policy(RetryPolicy::none()))
+    .get_ticker(get_ticker_params())
+    .await;
+  let hits = mock.hits_async().await;
+  let default = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  assert!(overridden.is_err());
+  assert!(default.is_err());
+  assert_eq!(hits, 1);
+  mock.assert_hits_async(1 + 3).await;
+}
+
+#[tokio::test]
+async fn bypass_cache_should_request_a_fresh_response() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default())
+    .build()
+    .unwrap();
+  let fresh = murray
+    .prices
+    .with_options(RequestOptions::default().bypass_cache());
+
+  // act
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  let bypassed = fresh
+    .with_meta()
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap();
+  let cached = murray
+    .prices
+    .with_meta()
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap();
+
+  // assert
+  mock.assert_hits_async(2).await;
+  assert_eq!(bypassed.cache_status(), CacheStatus::Bypass);
+  assert_eq!(cached.cache_status(), CacheStatus::Hit);
+}
+
+#[tokio::test]
+async fn base_url_should_send_the_calls_elsewhere() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let tenant = MockServer::start_async().await;
+  let usual = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let other = tenant
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default())
+    .build()
+    .unwrap();
+  let tenant_prices = murray
+    .prices
+    .with_options(RequestOptions::default().base_url(tenant.base_url()));
+
+  // act
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  let response = tenant_prices
+    .with_meta()
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap();
+  tenant_prices.get_ticker(get_ticker_params()).await.unwrap();
+
+  // assert
+  usual.assert_hits_async(1).await;
+  other.assert_hits_async(1).await;
+  assert_eq!(response.cache_status(), CacheStatus::Miss);
+  assert_eq!(
+    response.url().as_str(),
+    format!("{}/ticker?symbol=BTCUSD", tenant.base_url())
+  );
+}
+
+#[tokio::test]
+async fn base_url_failures_should_not_open_the_circuit_of_the_service() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let tenant = MockServer::start_async().await;
+  let usual = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  tenant
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(503);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(RetryPolicy::none())
+    .circuit_breaker(
+      Service::Prices,
+      CircuitBreakerConfig::default().failure_threshold(2),
+    )
+    .build()
+    .unwrap();
+  let tenant_prices = murray
+    .prices
+    .with_options(RequestOptions::default().base_url(tenant.base_url()));
+
+  // act
+  for _ in 0..3 {
+    let _ = tenant_prices.get_ticker(get_ticker_params()).await;
+  }
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  usual.assert_hits_async(1).await;
+  assert!(response.is_ok());
+  assert_eq!(murray.prices.circuit_state(), Some(CircuitState::Closed));
+  assert_eq!(tenant_prices.circuit_state(), Some(CircuitState::Open));
+}
+
+#[tokio::test]
+async fn base_url_failures_should_open_its_circuit_across_calls() {
+  // arrange
+  let tenant = MockServer::start_async().await;
+  let mock = tenant
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(503);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint("http://localhost:1")
+    .retry_policy(RetryPolicy::none())
+    .circuit_breaker(
+      Service::Prices,
+      CircuitBreakerConfig::default().failure_threshold(2),
+    )
+    .build()
+    .unwrap();
+  let options = RequestOptions::default().base_url(tenant.base_url());
+
+  // act
+  for _ in 0..2 {
+    let _ = murray
+      .prices
+      .with_options(options.clone())
+      .get_ticker(get_ticker_params())
+      .await;
+  }
+  let result = murray
+    .prices
+    .with_options(options)
+    .get_ticker(get_ticker_params())
+    .await;
+
+  // assert
+  mock.assert_hits_async(2).await;
+  assert!(result.unwrap_err().is_circuit_open());
+  assert_eq!(murray.prices.circuit_state(), Some(CircuitState::Closed));
+}
@@ -9,19 +9,19 @@ struct Sut {
 }
 
 impl Sut {
-  fn new() -> Self {
-    let server = MockServer::start();
+  async fn new() -> Self {
+    let server = MockServer::start_async().await;
     Sut { server }
   }
 
-  fn from(
+  async fn from(
     &self,
     path: &str,
     status: u16,
     method: Method,
     req_body: &str,
     res_body: &str,
-  ) -> (Mock, Murray) {
+  ) -> (Mock<'_>, Murray) {
     // Create a mock on the server.
     let mock = match method {
       Method::GET
@@ -29,20 +29,30 @@ impl Sut {
       | Method::DELETE
       | Method::OPTIONS
       | Method::TRACE
-      | Method::CONNECT => self.server.mock(|when, then| {
-        when.method(method).path(path);
-        then
-          .status(status)
-          .header("content-type", "application/json")
-          .body(res_body);
-      }),
-      Method::POST | Method::PATCH | Method::PUT => self.server.mock(|when, then| {
-        when.method(method).path(path).body(req_body);
-        then
-          .status(status)
-          .header("content-type", "application/json")
-          .body(res_body);
-      }),
+      | Method::CONNECT => {
+        self
+          .server
+          .mock_async(|when, then| {
+            when.method(method).path(path);
+            then
+              .status(status)
+              .header("content-type", "application/json")
+              .body(res_body);
+          })
+          .await
+      }
+      Method::POST | Method::PATCH | Method::PUT => {
+        self
+          .server
+          .mock_async(|when, then| {
+            when.method(method).path(path).body(req_body);
+            then
+              .status(status)
+              .header("content-type", "application/json")
+              .body(res_body);
+          })
+          .await
+      }
     };
 
     let mut murray = Murray::default();
@@ -53,15 +63,15 @@ impl Sut {
 }
 
 /// CONVERT CURRENCY
-#[test]
-fn convert_currency_should_return_successfully() {
+#[tokio::test]
+async fn convert_currency_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/prices/convert-response.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/convert", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/convert", 200, Method::GET, "", &body).await;
 
   // act
   let response = murray
@@ -70,20 +80,21 @@ fn convert_currency_should_return_successfully() {
       currency: murray_rs::Currency::BRL,
       value: 100,
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.btc, expected_response["btc"]);
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn convert_currency_should_return_error_when_problem_with_server() {
+async fn convert_currency_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/convert", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/convert", 400, Method::GET, "", &body).await;
 
   // act
   let _response = murray
@@ -92,16 +103,17 @@ fn convert_currency_should_return_error_when_problem_with_server() {
       currency: murray_rs::Currency::BRL,
       value: 100,
     })
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn convert_currency_should_return_error_when_body_returns_wrong_json() {
+async fn convert_currency_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/convert", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/convert", 200, Method::GET, "", &body).await;
 
   // act
   let _response = murray
@@ -110,164 +122,199 @@ fn convert_currency_should_return_error_when_body_returns_wrong_json() {
       currency: murray_rs::Currency::BRL,
       value: 100,
     })
+    .await
     .unwrap();
 }
 
 /// GET TICKER
-#[test]
-fn get_ticker_should_return_successfully() {
+#[tokio::test]
+async fn get_ticker_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/prices/get-ticker.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/ticker", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/ticker", 200, Method::GET, "", &body).await;
 
   // act
   let response = murray
     .prices
     .get_ticker(GetTickerParams {
-      symbol: murray_rs::Symbol::BTCBRL
+      symbol: murray_rs::Symbol::BTCBRL,
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.price, expected_response["price"]);
 }
 
-#[test]
+#[tokio::test]
+async fn get_ticker_should_keep_unknown_fields() {
+  // arrange
+  let body = r#"{"data": {"price": "339225.00000000", "change24h": null, "source": "binance", "symbol": "BTCBRL", "volume24h": "123.4"}}"#;
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/ticker", 200, Method::GET, "", body).await;
+
+  // act
+  let response = murray
+    .prices
+    .get_ticker(GetTickerParams {
+      symbol: murray_rs::Symbol::BTCBRL,
+    })
+    .await
+    .unwrap();
+
+  // assert
+  assert!(response.change24h.is_none());
+  assert_eq!(response.extra["volume24h"], "123.4");
+  assert_eq!(
+    serde_json::to_value(&response).unwrap()["volume24h"],
+    "123.4"
+  );
+}
+
+#[tokio::test]
 #[should_panic]
-fn get_ticker_should_return_error_when_problem_with_server() {
+async fn get_ticker_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/ticker", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/ticker", 400, Method::GET, "", &body).await;
 
   // act
   let _response = murray
     .prices
     .get_ticker(GetTickerParams {
-      symbol: murray_rs::Symbol::BTCBRL
+      symbol: murray_rs::Symbol::BTCBRL,
     })
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_ticker_should_return_error_when_body_returns_wrong_json() {
+async fn get_ticker_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/ticker", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/ticker", 200, Method::GET, "", &body).await;
 
   // act
   let _response = murray
     .prices
     .get_ticker(GetTickerParams {
-      symbol: murray_rs::Symbol::BTCBRL
+      symbol: murray_rs::Symbol::BTCBRL,
     })
+    .await
     .unwrap();
 }
 
 /// GET TICKERS
-#[test]
-fn get_tickers_should_return_successfully() {
+#[tokio::test]
+async fn get_tickers_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/prices/get-tickers.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/tickers", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/tickers", 200, Method::GET, "", &body).await;
 
   // act
   let response = murray
     .prices
     .get_tickers(GetTickerParams {
-      symbol: murray_rs::Symbol::BTCBRL
+      symbol: murray_rs::Symbol::BTCBRL,
     })
+    .await
     .unwrap();
 
   // assert
-  mock.assert();
-  assert_eq!(response.tickers[0].price, expected_response["tickers"][0]["price"]);
+  mock.assert_async().await;
+  assert_eq!(
+    response.tickers[0].price,
+    expected_response["tickers"][0]["price"]
+  );
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_tickers_should_return_error_when_problem_with_server() {
+async fn get_tickers_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/tickers", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/tickers", 400, Method::GET, "", &body).await;
 
   // act
   let _response = murray
     .prices
     .get_tickers(GetTickerParams {
-      symbol: murray_rs::Symbol::BTCBRL
+      symbol: murray_rs::Symbol::BTCBRL,
     })
+    .await
     .unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_tickers_should_return_error_when_body_returns_wrong_json() {
+async fn get_tickers_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/tickers", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/tickers", 200, Method::GET, "", &body).await;
 
   // act
   let _response = murray
     .prices
     .get_tickers(GetTickerParams {
-      symbol: murray_rs::Symbol::BTCBRL
+      symbol: murray_rs::Symbol::BTCBRL,
     })
+    .await
     .unwrap();
 }
 
 /// GET HEALTH
-#[test]
-fn get_health_should_return_successfully() {
+#[tokio::test]
+async fn get_health_should_return_successfully() {
   // arrange
   let expected_response =
     fs::read_to_string("tests/mocks/prices/get-health.json").expect("Unable to read file");
   let expected_response: Value = serde_json::from_str(&expected_response).expect("Unable to parse");
   let body = format!(r#"{{"data":  {}}}"#, expected_response);
-  let sut = Sut::new();
-  let (mock, murray) = sut.from("/health", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (mock, murray) = sut.from("/health", 200, Method::GET, "", &body).await;
 
   // act
-  let response = murray.prices.get_health().unwrap();
+  let response = murray.prices.get_health().await.unwrap();
 
   // assert
-  mock.assert();
+  mock.assert_async().await;
   assert_eq!(response.message, expected_response["message"]);
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_health_should_return_error_when_problem_with_server() {
+async fn get_health_should_return_error_when_problem_with_server() {
   // arrange
   let body = "".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/health", 400, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/health", 400, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.prices.get_health().unwrap();
+  let _response = murray.prices.get_health().await.unwrap();
 }
 
-#[test]
+#[tokio::test]
 #[should_panic]
-fn get_health_should_return_error_when_body_returns_wrong_json() {
+async fn get_health_should_return_error_when_body_returns_wrong_json() {
   // arrange
   let body = "wrong-return".to_string();
-  let sut = Sut::new();
-  let (_mock, murray) = sut.from("/health", 200, Method::GET, "", &body);
+  let sut = Sut::new().await;
+  let (_mock, murray) = sut.from("/health", 200, Method::GET, "", &body).await;
 
   // act
-  let _response = murray.prices.get_health().unwrap();
-}
\ No newline at end of file
+  let _response = murray.prices.get_health().await.unwrap();
+}
@@ -0,0 +1,144 @@
+mod common;
+
+use std::time::{Duration, Instant};
+
+use httpmock::prelude::*;
+use murray_rs::{ErrorKind, Murray, RateLimit, RequestOptions, Service};
+
+use common::body_from;
+
+async fn health_server() -> MockServer {
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health");
+      then
+        .status(200)
+        .header("content-type", "application/json")
+        .body(body_from("tests/mocks/blockchain/get-health.json"));
+    })
+    .await;
+  server
+}
+
+#[tokio::test]
+async fn rate_limit_should_fail_fast_when_no_permit_is_available() {
+  // arrange
+  let server = health_server().await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .rate_limit(
+      Service::Blockchain,
+      RateLimit::new(2, Duration::from_secs(60)).fail_fast(),
+    )
+    .build()
+    .unwrap();
+
+  // act
+  let first = murray.blockchain.get_health().await;
+  let second = murray.blockchain.get_health().await;
+  let third = murray.blockchain.get_health().await;
+
+  // assert
+  assert!(first.is_ok() && second.is_ok());
+  let error = third.unwrap_err();
+  assert_eq!(error.kind(), ErrorKind::RateLimited);
+  assert_eq!(error.service(), Service::Blockchain);
+  assert!(error.is_rate_limited());
+  assert!(error.retry_after().unwrap() > Duration::ZERO);
+}
+
+#[tokio::test]
+async fn rate_limit_should_wait_for_permit_by_default() {
+  // arrange
+  let server = health_server().await;
+  let murray = Murray::builder()
+    .lightning_endpoint(server.base_url())
+    .rate_limit(
+      Service::Lightning,
+      RateLimit::new(1, Duration::from_millis(200)),
+    )
+    .build()
+    .unwrap();
+  let start = Instant::now();
+
+  // act
+  for _ in 0..3 {
+    murray.lightning.get_health().await.unwrap();
+  }
+
+  // assert
+  assert!(start.elapsed() >= Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn rate_limit_should_hand_back_the_permit_of_a_cancelled_call() {
+  // arrange
+  let server = health_server().await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .rate_limit(
+      Service::Blockchain,
+      RateLimit::new(1, Duration::from_millis(400)),
+    )
+    .build()
+    .unwrap();
+  murray.blockchain.get_health().await.unwrap();
+  let start = Instant::now();
+
+  // act
+  let cancelled = murray
+    .blockchain
+    .with_options(RequestOptions::default().timeout(Duration::from_millis(50)))
+    .get_health()
+    .await;
+  murray.blockchain.get_health().await.unwrap();
+
+  // assert
+  assert_eq!(cancelled.unwrap_err().kind(), ErrorKind::DeadlineExceeded);
+  // the next call only waits for the permit the cancelled call didn't use
+  assert!(start.elapsed() < Duration::from_millis(700));
+}
+
+#[tokio::test]
+async fn rate_limit_should_be_shared_across_clones() {
+  // arrange
+  let server = health_server().await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .rate_limit(Service::Prices, RateLimit::per_minute(1).fail_fast())
+    .build()
+    .unwrap();
+  let clone = murray.clone();
+
+  // act
+  let from_clone = clone.prices.get_health().await;
+  let from_original = murray.prices.get_health().await;
+
+  // assert
+  assert!(from_clone.is_ok());
+  assert_eq!(from_original.unwrap_err().kind(), ErrorKind::RateLimited);
+}
+
+#[tokio::test]
+async fn rate_limit_should_only_apply_to_its_service() {
+  // arrange
+  let server = health_server().await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .prices_endpoint(server.base_url())
+    .rate_limit(Service::Prices, RateLimit::per_minute(1).fail_fast())
+    .build()
+    .unwrap();
+
+  // act
+  let prices = murray.prices.get_health().await;
+  let blockchain: Vec<_> = vec![
+    murray.blockchain.get_health().await,
+    murray.blockchain.get_health().await,
+  ];
+
+  // assert
+  assert!(prices.is_ok());
+  assert!(blockchain.iter().all(|r| r.is_ok()));
+}
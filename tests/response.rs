@@ -0,0 +1,127 @@
+mod common;
+
+use std::time::Duration;
+
+use httpmock::prelude::*;
+use murray_rs::{CacheConfig, CacheStatus, Murray, RetryPolicy, Service};
+
+use common::{get_ticker_params, ticker_body};
+
+#[tokio::test]
+async fn with_meta_should_return_the_http_response() {
+  // arrange
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then
+        .status(200)
+        .header("cache-control", "max-age=30")
+        .delay(Duration::from_millis(50))
+        .body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray
+    .prices
+    .with_meta()
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap();
+
+  // assert
+  assert_eq!(response.status(), 200);
+  assert_eq!(response.headers()["cache-control"], "max-age=30");
+  assert!(response.elapsed() >= Duration::from_millis(50));
+  assert_eq!(
+    response.url().as_str(),
+    format!("{}/ticker?symbol=BTCUSD", server.base_url())
+  );
+  assert_eq!(response.cache_status(), CacheStatus::Bypass);
+  assert_eq!(response.body(), ticker_body().as_bytes());
+  assert_eq!(response.data().symbol, "BTCBRL");
+}
+
+#[tokio::test]
+async fn with_meta_should_tell_the_cache_status() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then
+        .status(200)
+        .header("x-served-by", "origin")
+        .body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default())
+    .build()
+    .unwrap();
+
+  // act
+  let miss = murray
+    .prices
+    .with_meta()
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap();
+  let hit = murray
+    .prices
+    .with_meta()
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap();
+
+  // assert
+  mock.assert_hits_async(1).await;
+  assert_eq!(miss.cache_status(), CacheStatus::Miss);
+  assert_eq!(miss.headers()["x-served-by"], "origin");
+  assert_eq!(hit.cache_status(), CacheStatus::Hit);
+  assert_eq!(hit.status(), 200);
+  assert!(hit.headers().is_empty());
+  assert_eq!(hit.url(), miss.url());
+  assert_eq!(hit.body(), miss.body());
+}
+
+#[tokio::test]
+async fn with_meta_should_tell_which_endpoint_served_the_call() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let fallback = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/health");
+      then.status(503);
+    })
+    .await;
+  fallback
+    .mock_async(|when, then| {
+      when.method(GET).path("/health");
+      then.status(200).body(r#"{"data": {"message": "OK"}}"#);
+    })
+    .await;
+  let murray = Murray::builder()
+    .lightning_endpoint(server.base_url())
+    .fallback_endpoints(Service::Lightning, [fallback.base_url()])
+    .retry_policy(RetryPolicy::default().initial_backoff(Duration::from_millis(10)))
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray.lightning.with_meta().get_health().await.unwrap();
+
+  // assert
+  assert_eq!(
+    response.url().as_str(),
+    format!("{}/health", fallback.base_url())
+  );
+  assert_eq!(response.into_data().message, "OK");
+}
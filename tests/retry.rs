@@ -0,0 +1,229 @@
+mod common;
+
+use std::{
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+  },
+  time::{Duration, Instant},
+};
+
+use httpmock::prelude::*;
+use murray_rs::{Murray, PostTransactionParams, RetryPolicy};
+use tokio::{
+  io::{AsyncReadExt, AsyncWriteExt},
+  net::TcpListener,
+};
+
+use common::{get_ticker_params, ticker_body};
+
+/// Status, extra headers and body of a scripted response.
+type Reply = (u16, Vec<(&'static str, String)>, String);
+
+/// Minimal HTTP server answering each connection with
+/// the next response of a script, so that consecutive
+/// attempts of the same call can get different answers.
+struct ScriptedServer {
+  base_url: String,
+  hits: Arc<AtomicUsize>,
+}
+
+impl ScriptedServer {
+  async fn start(script: Vec<Reply>) -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let hits = Arc::new(AtomicUsize::new(0));
+
+    let counter = hits.clone();
+    tokio::spawn(async move {
+      for (status, headers, body) in script {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = socket.read(&mut buf).await;
+        counter.fetch_add(1, Ordering::SeqCst);
+
+        let mut response = format!(
+          "HTTP/1.1 {} Scripted\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n",
+          status,
+          body.len()
+        );
+        for (name, value) in headers {
+          response.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        response.push_str("\r\n");
+        response.push_str(&body);
+        let _ = socket.write_all(response.as_bytes()).await;
+        let _ = socket.shutdown().await;
+      }
+    });
+
+    Self { base_url, hits }
+  }
+
+  fn hits(&self) -> usize {
+    self.hits.load(Ordering::SeqCst)
+  }
+}
+
+fn fast_policy() -> RetryPolicy {
+  RetryPolicy::default()
+    .initial_backoff(Duration::from_millis(10))
+    .jitter(false)
+}
+
+#[tokio::test]
+async fn retry_should_return_successfully_after_transient_failures() {
+  // arrange
+  let server = ScriptedServer::start(vec![
+    (503, vec![], "".to_string()),
+    (502, vec![], "".to_string()),
+    (200, vec![], ticker_body()),
+  ])
+  .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url.clone())
+    .retry_policy(fast_policy())
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  assert!(response.is_ok());
+  assert_eq!(server.hits(), 3);
+}
+
+#[tokio::test]
+async fn retry_should_honor_retry_after_header() {
+  // arrange
+  let server = ScriptedServer::start(vec![
+    (429, vec![("retry-after", "1".to_string())], "".to_string()),
+    (200, vec![], ticker_body()),
+  ])
+  .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url.clone())
+    .retry_policy(fast_policy())
+    .build()
+    .unwrap();
+  let start = Instant::now();
+
+  // act
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  assert!(response.is_ok());
+  assert!(start.elapsed() >= Duration::from_secs(1));
+  assert_eq!(server.hits(), 2);
+}
+
+#[tokio::test]
+async fn retry_should_give_up_after_max_attempts() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(503);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(fast_policy().max_attempts(4))
+    .build()
+    .unwrap();
+
+  // act
+  let error = murray
+    .prices
+    .get_ticker(get_ticker_params())
+    .await
+    .unwrap_err();
+
+  // assert
+  mock.assert_hits_async(4).await;
+  assert_eq!(error.status().map(|s| s.as_u16()), Some(503));
+}
+
+#[tokio::test]
+async fn retry_should_not_retry_non_retryable_statuses() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(400);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(fast_policy())
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  mock.assert_hits_async(1).await;
+  assert!(response.is_err());
+}
+
+#[tokio::test]
+async fn retry_should_not_retry_post_transaction_by_default() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(POST).path("/tx");
+      then.status(503);
+    })
+    .await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .retry_policy(fast_policy())
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray
+    .blockchain
+    .post_transaction(PostTransactionParams {
+      tx_hex: "some-tx-hex".to_string(),
+    })
+    .await;
+
+  // assert
+  mock.assert_hits_async(1).await;
+  assert!(response.is_err());
+}
+
+#[tokio::test]
+async fn retry_should_retry_post_transaction_when_opted_in() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(POST).path("/tx");
+      then.status(503);
+    })
+    .await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .retry_policy(fast_policy().retry_post(true))
+    .build()
+    .unwrap();
+
+  // act
+  let response = murray
+    .blockchain
+    .post_transaction(PostTransactionParams {
+      tx_hex: "some-tx-hex".to_string(),
+    })
+    .await;
+
+  // assert
+  mock.assert_hits_async(3).await;
+  assert!(response.is_err());
+}
@@ -0,0 +1,122 @@
+mod common;
+
+use std::time::Duration;
+
+use httpmock::{prelude::*, Mock};
+use murray_rs::{GetNodeDetailsParams, Murray};
+
+use common::body_from;
+
+async fn mock_slow_fees(server: &MockServer, status: u16) -> Mock<'_> {
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/fees/recommended");
+      then
+        .status(status)
+        .delay(Duration::from_millis(200))
+        .body(body_from("tests/mocks/blockchain/fees-recommended.json"));
+    })
+    .await
+}
+
+/// Calls `get_fees_recommended` from `tasks` tasks at once.
+async fn concurrent_fees(murray: &Murray, tasks: usize) -> Vec<bool> {
+  let handles: Vec<_> = (0..tasks)
+    .map(|_| {
+      let murray = murray.clone();
+      tokio::spawn(async move { murray.blockchain.get_fees_recommended().await.is_ok() })
+    })
+    .collect();
+
+  let mut results = vec![];
+  for handle in handles {
+    results.push(handle.await.unwrap());
+  }
+  results
+}
+
+#[tokio::test]
+async fn single_flight_should_collapse_concurrent_identical_requests() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = mock_slow_fees(&server, 200).await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  let results = concurrent_fees(&murray, 10).await;
+
+  // assert
+  assert!(results.iter().all(|ok| *ok));
+  mock.assert_hits_async(1).await;
+}
+
+#[tokio::test]
+async fn single_flight_should_fan_out_errors_to_all_waiters() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = mock_slow_fees(&server, 400).await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  let results = concurrent_fees(&murray, 5).await;
+
+  // assert
+  assert!(results.iter().all(|ok| !*ok));
+  mock.assert_hits_async(1).await;
+}
+
+#[tokio::test]
+async fn single_flight_should_be_disableable() {
+  // arrange
+  let server = MockServer::start_async().await;
+  let mock = mock_slow_fees(&server, 200).await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .single_flight(false)
+    .build()
+    .unwrap();
+
+  // act
+  concurrent_fees(&murray, 5).await;
+
+  // assert
+  mock.assert_hits_async(5).await;
+}
+
+#[tokio::test]
+async fn single_flight_should_not_collapse_different_paths() {
+  // arrange
+  let body = body_from("tests/mocks/lightning/node-details.json");
+  let server = MockServer::start_async().await;
+  let mock = server
+    .mock_async(|when, then| {
+      when.method(GET).path_contains("/node/");
+      then
+        .status(200)
+        .delay(Duration::from_millis(200))
+        .body(body);
+    })
+    .await;
+  let murray = Murray::builder()
+    .lightning_endpoint(server.base_url())
+    .build()
+    .unwrap();
+  let details = |public_key: &str| {
+    murray.lightning.get_node_details(GetNodeDetailsParams {
+      public_key: public_key.to_string(),
+    })
+  };
+
+  // act
+  let (first, second, same_as_first) = tokio::join!(details("a"), details("b"), details("a"));
+
+  // assert
+  assert!(first.is_ok() && second.is_ok() && same_as_first.is_ok());
+  mock.assert_hits_async(2).await;
+}
@@ -0,0 +1,214 @@
+#![cfg(feature = "tracing")]
+
+mod common;
+
+use std::{
+  collections::HashMap,
+  fmt,
+  sync::{Arc, Mutex},
+  time::Duration,
+};
+
+use httpmock::prelude::*;
+use murray_rs::{CacheConfig, GetAddressParams, Murray, RetryPolicy};
+use tracing::{
+  field::{Field, Visit},
+  span::{Attributes, Id, Record},
+  Subscriber,
+};
+use tracing_subscriber::{layer::Context, prelude::*, registry::LookupSpan, Layer};
+
+use common::{body_from, get_ticker_params, ticker_body};
+
+type Fields = HashMap<String, String>;
+
+/// Collects the fields of every `murray.request` span, in creation order.
+#[derive(Clone, Default)]
+struct SpanRecorder {
+  spans: Arc<Mutex<Vec<Fields>>>,
+  /// Index in `spans` of each open span, as ids get reused.
+  open: Arc<Mutex<HashMap<u64, usize>>>,
+}
+
+impl SpanRecorder {
+  fn spans(&self) -> Vec<Fields> {
+    self.spans.lock().unwrap().clone()
+  }
+}
+
+struct FieldVisitor<'a>(&'a mut Fields);
+
+impl Visit for FieldVisitor<'_> {
+  fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+    self
+      .0
+      .insert(field.name().to_string(), format!("{:?}", value));
+  }
+
+  fn record_str(&mut self, field: &Field, value: &str) {
+    self.0.insert(field.name().to_string(), value.to_string());
+  }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanRecorder {
+  fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _ctx: Context<'_, S>) {
+    if attrs.metadata().name() != "murray.request" {
+      return;
+    }
+    let mut fields = Fields::new();
+    attrs.record(&mut FieldVisitor(&mut fields));
+    let mut spans = self.spans.lock().unwrap();
+    self.open.lock().unwrap().insert(id.into_u64(), spans.len());
+    spans.push(fields);
+  }
+
+  fn on_record(&self, id: &Id, values: &Record<'_>, _ctx: Context<'_, S>) {
+    if let Some(index) = self.open.lock().unwrap().get(&id.into_u64()) {
+      values.record(&mut FieldVisitor(&mut self.spans.lock().unwrap()[*index]));
+    }
+  }
+
+  fn on_close(&self, id: Id, _ctx: Context<'_, S>) {
+    self.open.lock().unwrap().remove(&id.into_u64());
+  }
+}
+
+#[tokio::test]
+async fn tracing_should_record_a_span_per_call() {
+  // arrange
+  let recorder = SpanRecorder::default();
+  let _guard = tracing_subscriber::registry()
+    .with(recorder.clone())
+    .set_default();
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .build()
+    .unwrap();
+
+  // act
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+
+  // assert
+  let spans = recorder.spans();
+  assert_eq!(spans.len(), 1);
+  let span = &spans[0];
+  assert_eq!(span["otel.name"], "GET /ticker");
+  assert_eq!(span["murray.service"], "prices");
+  assert_eq!(span["murray.endpoint"], "/ticker");
+  assert_eq!(span["url.path"], "/ticker");
+  assert_eq!(span["url.query"], "symbol=BTCUSD");
+  assert_eq!(span["http.response.status_code"], "200");
+  assert_eq!(span["murray.attempts"], "1");
+  assert_eq!(span["otel.status_code"], "OK");
+  assert!(span.contains_key("murray.latency_ms"));
+}
+
+#[tokio::test]
+async fn tracing_should_record_retries_and_errors() {
+  // arrange
+  let recorder = SpanRecorder::default();
+  let _guard = tracing_subscriber::registry()
+    .with(recorder.clone())
+    .set_default();
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(503);
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .retry_policy(
+      RetryPolicy::default()
+        .initial_backoff(Duration::from_millis(10))
+        .jitter(false),
+    )
+    .build()
+    .unwrap();
+
+  // act
+  let _ = murray.prices.get_ticker(get_ticker_params()).await;
+
+  // assert
+  let span = &recorder.spans()[0];
+  assert_eq!(span["murray.attempts"], "3");
+  assert_eq!(span["http.response.status_code"], "503");
+  assert_eq!(span["otel.status_code"], "ERROR");
+  assert_eq!(span["error.type"], "API error");
+}
+
+#[tokio::test]
+async fn tracing_should_record_cache_hits() {
+  // arrange
+  let recorder = SpanRecorder::default();
+  let _guard = tracing_subscriber::registry()
+    .with(recorder.clone())
+    .set_default();
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/ticker");
+      then.status(200).body(ticker_body());
+    })
+    .await;
+  let murray = Murray::builder()
+    .prices_endpoint(server.base_url())
+    .cache(CacheConfig::default())
+    .build()
+    .unwrap();
+
+  // act
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+  murray.prices.get_ticker(get_ticker_params()).await.unwrap();
+
+  // assert
+  let spans = recorder.spans();
+  assert_eq!(spans[0]["murray.cache"], "miss");
+  assert_eq!(spans[1]["murray.cache"], "hit");
+  assert!(!spans[1].contains_key("http.response.status_code"));
+}
+
+#[tokio::test]
+async fn tracing_should_redact_addresses_when_asked() {
+  // arrange
+  let recorder = SpanRecorder::default();
+  let _guard = tracing_subscriber::registry()
+    .with(recorder.clone())
+    .set_default();
+  let server = MockServer::start_async().await;
+  server
+    .mock_async(|when, then| {
+      when.method(GET).path("/address/some-address");
+      then
+        .status(200)
+        .body(body_from("tests/mocks/blockchain/get-address-details.json"));
+    })
+    .await;
+  let murray = Murray::builder()
+    .blockchain_endpoint(server.base_url())
+    .redact_addresses(true)
+    .build()
+    .unwrap();
+
+  // act
+  murray
+    .blockchain
+    .get_address_details(GetAddressParams {
+      address: "some-address".to_string(),
+    })
+    .await
+    .unwrap();
+
+  // assert
+  let span = &recorder.spans()[0];
+  assert_eq!(span["url.path"], "/address/{address}");
+  assert!(span.values().all(|value| !value.contains("some-address")));
+}